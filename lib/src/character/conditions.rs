@@ -0,0 +1,371 @@
+use chrono::NaiveDate;
+use cofd_schema::traits::DerivedTrait;
+use cofd_util::{AllVariants, VariantName};
+use serde::{Deserialize, Serialize};
+
+use super::{
+	Character,
+	modifier::{
+		Condition as ModifierCondition, Dependency, Modifier, ModifierOp, ModifierTarget, RollTag,
+	},
+};
+use crate::prelude::Trait;
+
+/// Beats awarded for resolving a Condition.
+pub const CONDITION_BEATS: u16 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, AllVariants, VariantName)]
+pub enum Condition {
+	Guilty,
+	Shaken,
+	Inspired,
+	Informed,
+	Leveraged,
+	Spooked,
+	Steadfast,
+	Swooning,
+	Wanton,
+	Connected,
+	Notoriety,
+
+	// Persistent
+	Addicted,
+	Amnesia,
+	Blind,
+	Broken,
+	Fugue,
+	Madness,
+	Mute,
+	Obsession,
+
+	/// Also reads the free-text Conditions of sheets saved before they were typed.
+	#[serde(untagged)]
+	Custom(String),
+}
+
+impl Default for Condition {
+	fn default() -> Self {
+		Condition::Custom(String::new())
+	}
+}
+
+impl Condition {
+	/// Persistent Conditions stay until their cause is dealt with, and can't be
+	/// resolved in a single scene.
+	pub fn is_persistent(&self) -> bool {
+		matches!(
+			self,
+			Condition::Addicted
+				| Condition::Amnesia
+				| Condition::Blind
+				| Condition::Broken
+				| Condition::Fugue
+				| Condition::Madness
+				| Condition::Mute
+				| Condition::Obsession
+		)
+	}
+
+	/// Beats awarded when the Condition is resolved.
+	pub fn beats(&self) -> u16 {
+		CONDITION_BEATS
+	}
+
+	/// How the Condition is resolved.
+	pub fn resolution(&self) -> &str {
+		match self {
+			Condition::Guilty => "Confess your crime, or make restitution for it.",
+			Condition::Shaken => "Give in to fear and fail a roll, or flee the scene.",
+			Condition::Inspired => {
+				"Spend Willpower on a roll for an exceptional success on three successes."
+			}
+			Condition::Informed => "Use the information to gain an exceptional success on a 3.",
+			Condition::Leveraged => {
+				"Do what the other character demands, or turn the tables on them."
+			}
+			Condition::Spooked => "Investigate the strange occurrence, or let it drive you away.",
+			Condition::Steadfast => "Use the confidence to turn a failed roll into a success.",
+			Condition::Swooning => "Give in to the object of your affection.",
+			Condition::Wanton => "Indulge your desires at a cost to your goals.",
+			Condition::Connected => "Call on the group for a favor that costs them.",
+			Condition::Notoriety => "Let your reputation cost you a roll, or win it back.",
+			Condition::Addicted => "Break the addiction, usually over several chapters.",
+			Condition::Amnesia => "Remember what was lost.",
+			Condition::Blind => "Regain sight, usually through supernatural means.",
+			Condition::Broken => "Find a way to reassert control over your life.",
+			Condition::Fugue => "Come to terms with the trauma that caused it.",
+			Condition::Madness => "Treat the underlying mental illness.",
+			Condition::Mute => "Regain your voice.",
+			Condition::Obsession => "Indulge or overcome the obsession.",
+			Condition::Custom(_) => "",
+		}
+	}
+
+	/// Mechanical effects of the Condition. Those that only change how a scene plays
+	/// out, or grant exceptional successes like Inspired, have none.
+	pub fn get_modifiers(&self) -> Vec<Modifier> {
+		match self {
+			Condition::Guilty | Condition::Swooning => {
+				vec![roll_penalty(-2, RollTag::SocialDefense)]
+			}
+			Condition::Shaken => vec![roll_penalty(-2, RollTag::Fear)],
+			Condition::Blind => vec![Modifier::new(
+				ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Perception)),
+				-3,
+				ModifierOp::Add,
+			)],
+			_ => vec![],
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, AllVariants, VariantName)]
+pub enum Tilt {
+	// Personal
+	ArmWrack,
+	BeatenDown,
+	Blinded,
+	Deafened,
+	Drugged,
+	Immobilized,
+	Insane,
+	KnockedDown,
+	LegWrack,
+	Poisoned,
+	/// Moderately sick.
+	Sick,
+	GravelySick,
+	Stunned,
+
+	// Environmental
+	Blizzard,
+	Earthquake,
+	ExtremeCold,
+	ExtremeHeat,
+	Flooded,
+	HeavyRain,
+	HeavyWinds,
+	Ice,
+
+	Custom(String),
+}
+
+impl Tilt {
+	/// Environmental Tilts affect everyone in the scene.
+	pub fn is_environmental(&self) -> bool {
+		matches!(
+			self,
+			Tilt::Blizzard
+				| Tilt::Earthquake
+				| Tilt::ExtremeCold
+				| Tilt::ExtremeHeat
+				| Tilt::Flooded
+				| Tilt::HeavyRain
+				| Tilt::HeavyWinds
+				| Tilt::Ice
+		)
+	}
+
+	/// How the Tilt ends.
+	pub fn resolution(&self) -> &str {
+		match self {
+			Tilt::ArmWrack | Tilt::LegWrack => "Ends when the damage that caused it is healed.",
+			Tilt::BeatenDown => "Surrender, or spend Willpower to take violent action.",
+			Tilt::Blinded | Tilt::Deafened => "Ends after the scene, or when the cause is healed.",
+			Tilt::Drugged | Tilt::Poisoned | Tilt::Sick | Tilt::GravelySick => {
+				"Ends when the substance or illness runs its course."
+			}
+			Tilt::Immobilized => "Break free with a successful roll.",
+			Tilt::Insane => "Ends at the end of the scene.",
+			Tilt::KnockedDown => "Stand up, using your action.",
+			Tilt::Stunned => "Ends after losing your next action.",
+			Tilt::Blizzard
+			| Tilt::Earthquake
+			| Tilt::ExtremeCold
+			| Tilt::ExtremeHeat
+			| Tilt::Flooded
+			| Tilt::HeavyRain
+			| Tilt::HeavyWinds
+			| Tilt::Ice => "Ends when the weather does, or when you leave it.",
+			Tilt::Custom(_) => "",
+		}
+	}
+
+	/// Mechanical effects of the Tilt. Those that restrict actions or deal damage, like
+	/// Beaten Down or Poisoned, have none.
+	pub fn get_modifiers(&self) -> Vec<Modifier> {
+		let defense = ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Defense));
+		let speed = ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Speed));
+		let perception = ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Perception));
+
+		match self {
+			Tilt::ArmWrack => vec![roll_penalty(-2, RollTag::InjuredArm)],
+			Tilt::LegWrack => vec![
+				Modifier::new(defense, -2, ModifierOp::Add),
+				Modifier::new(speed, 2, ModifierOp::Div),
+			],
+			Tilt::KnockedDown => vec![roll_penalty(
+				-2,
+				ModifierCondition::Any(
+					[
+						RollTag::Firearm,
+						RollTag::Melee,
+						RollTag::Unarmed,
+						RollTag::Thrown,
+					]
+					.into_iter()
+					.map(ModifierCondition::Tag)
+					.collect(),
+				),
+			)],
+			Tilt::Blinded | Tilt::Deafened | Tilt::HeavyRain => {
+				vec![Modifier::new(perception, -3, ModifierOp::Add)]
+			}
+			Tilt::Drugged => vec![Modifier::new(ModifierTarget::Roll, -2, ModifierOp::Add)],
+			Tilt::Immobilized => vec![
+				Modifier::new(defense, 0, ModifierOp::Set),
+				Modifier::new(speed, 0, ModifierOp::Set),
+			],
+			Tilt::Sick | Tilt::ExtremeCold | Tilt::ExtremeHeat => {
+				vec![Modifier::new(ModifierTarget::Roll, -1, ModifierOp::Add)]
+			}
+			Tilt::GravelySick => vec![Modifier::new(ModifierTarget::Roll, -3, ModifierOp::Add)],
+			Tilt::Stunned => vec![Modifier::new(defense, 2, ModifierOp::Div)],
+			Tilt::Ice => vec![Modifier::new(speed, 2, ModifierOp::Div)],
+			_ => vec![],
+		}
+	}
+}
+
+/// A penalty to rolls made under `condition`.
+fn roll_penalty(value: i16, condition: impl Into<ModifierCondition>) -> Modifier {
+	Modifier::conditional(ModifierTarget::Roll, value, ModifierOp::Add, condition)
+}
+
+impl Character {
+	pub fn add_condition(&mut self, condition: Condition) {
+		self.conditions.push(condition);
+		self.invalidate(Dependency::Conditions);
+	}
+
+	/// Resolves a Condition, awarding its Beats.
+	///
+	/// Returns the index of the ledger entry recording the Beats,
+	/// or `None` if the character doesn't have the Condition.
+	pub fn resolve_condition(&mut self, condition: &Condition, date: NaiveDate) -> Option<usize> {
+		let i = self.conditions.iter().position(|c| c == condition)?;
+		let condition = self.conditions.remove(i);
+		self.invalidate(Dependency::Conditions);
+
+		Some(self.award_beats(
+			date,
+			format!("Resolved {}", condition.name()),
+			condition.beats(),
+		))
+	}
+
+	pub fn add_tilt(&mut self, tilt: Tilt) {
+		self.tilts.push(tilt);
+		self.invalidate(Dependency::Conditions);
+	}
+
+	/// Ends a Tilt, returning whether the character had it.
+	pub fn remove_tilt(&mut self, tilt: &Tilt) -> bool {
+		let Some(i) = self.tilts.iter().position(|t| t == tilt) else {
+			return false;
+		};
+
+		self.tilts.remove(i);
+		self.invalidate(Dependency::Conditions);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		character::{Attributes, modifier::RollContext},
+		dice_pool::PreparedRoll,
+		prelude::{Attribute, Skill},
+	};
+
+	#[test]
+	fn conditions() {
+		let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+		let mut character = Character::builder()
+			.with_attributes(Attributes {
+				wits: 3,
+				composure: 3,
+				..Default::default()
+			})
+			.build();
+		let perception = character.perception();
+
+		character.add_condition(Condition::Guilty);
+		character.add_tilt(Tilt::Blinded);
+		assert_eq!(character.perception(), perception - 3);
+
+		assert_eq!(character.resolve_condition(&Condition::Shaken, date), None);
+		character
+			.resolve_condition(&Condition::Guilty, date)
+			.unwrap();
+		assert!(character.conditions.is_empty());
+		assert_eq!(character.available_beats(), CONDITION_BEATS);
+
+		assert!(character.remove_tilt(&Tilt::Blinded));
+		assert_eq!(character.perception(), perception);
+	}
+
+	#[test]
+	fn modifiers() {
+		let mut character = Character::builder()
+			.with_attributes(Attributes {
+				wits: 3,
+				dexterity: 3,
+				strength: 2,
+				..Default::default()
+			})
+			.build();
+		let pool = Attribute::Strength + Skill::Brawl;
+		let melee = RollContext::default().with_tag(RollTag::Melee);
+		let social = RollContext::default().with_tag(RollTag::SocialDefense);
+		let (defense, speed) = (character.defense(), character.speed());
+
+		character.add_tilt(Tilt::LegWrack);
+		assert_eq!(character.defense(), defense - 2);
+		assert_eq!(character.speed(), speed / 2);
+
+		character.add_tilt(Tilt::KnockedDown);
+		character.add_condition(Condition::Guilty);
+		assert_eq!(PreparedRoll::new(&pool, &character).dice(), 2);
+		assert_eq!(
+			PreparedRoll::in_context(&pool, &character, &melee).dice(),
+			0
+		);
+		assert_eq!(
+			PreparedRoll::in_context(&pool, &character, &social).modifiers,
+			[("guilty".to_owned(), -2)]
+		);
+
+		character.add_tilt(Tilt::GravelySick);
+		assert_eq!(PreparedRoll::new(&pool, &character).penalty(), 3);
+	}
+
+	#[test]
+	fn old_conditions() {
+		let character: Character =
+			ron::from_str(r#"(conditions: ["Guilty", "Hungover"])"#).unwrap();
+		assert_eq!(
+			character.conditions,
+			[Condition::Guilty, Condition::Custom("Hungover".to_owned())]
+		);
+
+		let ron = ron::to_string(&character).unwrap();
+		let character: Character = ron::from_str(&ron).unwrap();
+		assert_eq!(
+			character.conditions,
+			[Condition::Guilty, Condition::Custom("Hungover".to_owned())]
+		);
+	}
+}
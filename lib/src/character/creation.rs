@@ -0,0 +1,390 @@
+use cofd_schema::traits::TraitCategory;
+use serde::{Deserialize, Serialize};
+
+use super::Character;
+use crate::{
+	prelude::{Attribute, Skill},
+	splat::{Splat, SplatTrait, ability::Ability},
+};
+
+/// Attribute dots to distribute between the three categories, on top of the free dot.
+pub const ATTRIBUTE_PRIORITIES: [u16; 3] = [5, 4, 3];
+pub const SKILL_PRIORITIES: [u16; 3] = [11, 7, 4];
+pub const SPECIALTIES: usize = 3;
+pub const MERIT_DOTS: u16 = 7;
+/// Merit dots traded for each dot of Supernatural Tolerance above 1.
+pub const MERIT_DOTS_PER_TOLERANCE: u16 = 5;
+
+const CATEGORIES: [TraitCategory; 3] = [
+	TraitCategory::Mental,
+	TraitCategory::Physical,
+	TraitCategory::Social,
+];
+
+/// A single way a character breaks the character creation rules.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum CreationViolation {
+	/// Dots spent per category, Mental, Physical and Social, aren't 5/4/3 in any order.
+	AttributePriorities {
+		spent: [u16; 3],
+	},
+	/// Dots spent per category aren't 11/7/4 in any order.
+	SkillPriorities {
+		spent: [u16; 3],
+	},
+	Specialties {
+		count: usize,
+		expected: usize,
+	},
+	MeritDots {
+		spent: u16,
+		expected: u16,
+	},
+	/// The template's free Attribute dot is on an Attribute it can't go to.
+	AttributeBonus {
+		attribute: Attribute,
+		allowed: Vec<Attribute>,
+	},
+	/// The free auspice Skill dot hasn't been chosen.
+	MissingSkillBonus {
+		allowed: Vec<Skill>,
+	},
+	AbilityDots {
+		spent: u16,
+		expected: u16,
+	},
+	/// Fewer dots than required in the template's favored abilities,
+	/// e.g. in-clan Disciplines or Ruling Arcana.
+	FavoredAbilityDots {
+		spent: u16,
+		required: u16,
+	},
+	/// An ability that must have at least a dot, e.g. auspice Renown.
+	MissingAbility {
+		ability: Ability,
+	},
+	/// An ability that can't be taken at creation, e.g. the Inferior Arcanum.
+	ForbiddenAbility {
+		ability: Ability,
+	},
+	AbilityAboveMaximum {
+		ability: Ability,
+		rating: u16,
+		max: u16,
+	},
+}
+
+/// Dots spent on a trait starting at `base`, where the fifth dot costs two.
+fn creation_cost(base: u16, rating: u16) -> u16 {
+	rating.saturating_sub(base) + u16::from(rating >= 5)
+}
+
+fn check_priorities(mut spent: [u16; 3], expected: [u16; 3]) -> bool {
+	spent.sort_unstable_by(|a, b| b.cmp(a));
+	spent == expected
+}
+
+impl Character {
+	/// Checks the character against the character creation rules, returning every
+	/// violation found. An empty list means the character is valid.
+	pub fn creation_violations(&self) -> Vec<CreationViolation> {
+		let mut violations = Vec::new();
+
+		let attributes = CATEGORIES.map(|category| {
+			Attribute::get_by_category(category)
+				.iter()
+				.map(|attr| creation_cost(1, *self._attributes.get(attr)))
+				.sum()
+		});
+		if !check_priorities(attributes, ATTRIBUTE_PRIORITIES) {
+			violations.push(CreationViolation::AttributePriorities { spent: attributes });
+		}
+
+		let skills = CATEGORIES.map(|category| {
+			Skill::get_by_category(category)
+				.into_iter()
+				.map(|skill| creation_cost(0, self.skills.get(skill)))
+				.sum()
+		});
+		if !check_priorities(skills, SKILL_PRIORITIES) {
+			violations.push(CreationViolation::SkillPriorities { spent: skills });
+		}
+
+		let specialties = self.specialties.values().map(Vec::len).sum();
+		if specialties != SPECIALTIES {
+			violations.push(CreationViolation::Specialties {
+				count: specialties,
+				expected: SPECIALTIES,
+			});
+		}
+
+		// Merits stored as abilities cost merit dots too.
+		let merits = self.merits.iter().map(|(_, rating)| rating).sum::<u16>()
+			+ self.ability_dots(|ability| matches!(ability, Ability::Merit(_)))
+			+ self.power.saturating_sub(1) * MERIT_DOTS_PER_TOLERANCE;
+		if merits != MERIT_DOTS {
+			violations.push(CreationViolation::MeritDots {
+				spent: merits,
+				expected: MERIT_DOTS,
+			});
+		}
+
+		self.splat_violations(&mut violations);
+
+		violations
+	}
+
+	/// Whether the character follows the character creation rules.
+	pub fn is_valid_creation(&self) -> bool {
+		self.creation_violations().is_empty()
+	}
+
+	/// Whether the character is still being created, i.e. hasn't earned or spent any
+	/// Experience yet, whether in the ledger or as untracked Beats.
+	pub fn is_in_creation(&self) -> bool {
+		self.ledger.is_empty() && self.beats == 0 && self.alternate_beats == 0
+	}
+
+	fn ability_dots(&self, filter: impl Fn(&Ability) -> bool) -> u16 {
+		self.abilities
+			.iter()
+			.filter(|(ability, _)| filter(ability))
+			.map(|(_, rating)| rating)
+			.sum()
+	}
+
+	fn check_ability_dots(
+		&self,
+		violations: &mut Vec<CreationViolation>,
+		expected: u16,
+		filter: impl Fn(&Ability) -> bool,
+	) {
+		let spent = self.ability_dots(filter);
+		if spent != expected {
+			violations.push(CreationViolation::AbilityDots { spent, expected });
+		}
+	}
+
+	fn require_ability(&self, violations: &mut Vec<CreationViolation>, ability: Ability) {
+		if self.abilities.get(&ability).copied().unwrap_or(0) == 0 {
+			violations.push(CreationViolation::MissingAbility { ability });
+		}
+	}
+
+	fn splat_violations(&self, violations: &mut Vec<CreationViolation>) {
+		let attr_bonus = match &self.splat {
+			Splat::Vampire(vampire) => Some((
+				*vampire.attr_bonus(),
+				vampire.clan.favored_attributes().to_vec(),
+			)),
+			Splat::Mage(mage) => Some((
+				*mage.attr_bonus(),
+				vec![Attribute::Resolve, Attribute::Stamina, Attribute::Composure],
+			)),
+			Splat::Changeling(changeling) => Some((
+				*changeling.attr_bonus(),
+				changeling.seeming.favored_attributes().to_vec(),
+			)),
+			_ => None,
+		};
+		if let Some((attribute, allowed)) = attr_bonus
+			&& !allowed.contains(&attribute)
+		{
+			violations.push(CreationViolation::AttributeBonus { attribute, allowed });
+		}
+
+		match &self.splat {
+			// Three dots of Disciplines, at least two of them in-clan.
+			Splat::Vampire(vampire) => {
+				self.check_ability_dots(violations, 3, |ability| {
+					matches!(ability, Ability::Discipline(_))
+				});
+
+				let spent = self.ability_dots(|ability| vampire.is_affinity(ability));
+				if spent < 2 {
+					violations.push(CreationViolation::FavoredAbilityDots { spent, required: 2 });
+				}
+			}
+			// Six dots of Arcana, three of them in the Ruling Arcana, none above 3
+			// and none in the Inferior Arcanum.
+			Splat::Mage(mage) => {
+				self.check_ability_dots(violations, 6, |ability| {
+					matches!(ability, Ability::Arcanum(_))
+				});
+
+				let ruling = mage.path.get_ruling_arcana();
+				let spent = self.ability_dots(
+					|ability| matches!(ability, Ability::Arcanum(arcanum) if ruling.contains(arcanum)),
+				);
+				if spent < 3 {
+					violations.push(CreationViolation::FavoredAbilityDots { spent, required: 3 });
+				}
+
+				for (ability, rating) in &self.abilities {
+					let Ability::Arcanum(arcanum) = ability else {
+						continue;
+					};
+
+					if arcanum == mage.path.get_inferior_arcanum() {
+						violations.push(CreationViolation::ForbiddenAbility {
+							ability: ability.clone(),
+						});
+					} else if *rating > 3 {
+						violations.push(CreationViolation::AbilityAboveMaximum {
+							ability: ability.clone(),
+							rating: *rating,
+							max: 3,
+						});
+					}
+				}
+			}
+			// A dot each of auspice and tribal Renown, plus one more of any Renown.
+			Splat::Werewolf(werewolf) => {
+				self.check_ability_dots(violations, 3, |ability| {
+					matches!(ability, Ability::Renown(_))
+				});
+
+				if let Some(auspice) = &werewolf.auspice {
+					self.require_ability(violations, auspice.get_renown().clone().into());
+
+					if werewolf.skill_bonus().is_none() {
+						violations.push(CreationViolation::MissingSkillBonus {
+							allowed: auspice.skills().to_vec(),
+						});
+					}
+				}
+				if let Some(tribe) = &werewolf.tribe {
+					self.require_ability(violations, tribe.get_renown().clone().into());
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		character::{Attributes, Skills},
+		splat::{
+			Merit,
+			mage::{Arcanum, Mage, Path},
+			vampire::{Clan, Discipline, Vampire},
+		},
+	};
+
+	fn attributes() -> Attributes {
+		Attributes {
+			intelligence: 3,
+			wits: 3,
+			resolve: 2,
+			strength: 2,
+			dexterity: 3,
+			stamina: 2,
+			presence: 2,
+			manipulation: 2,
+			composure: 2,
+		}
+	}
+
+	fn skills() -> Skills {
+		Skills {
+			academics: 3,
+			computer: 2,
+			occult: 5,
+			athletics: 2,
+			brawl: 2,
+			stealth: 3,
+			empathy: 2,
+			persuasion: 2,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn mortal() {
+		let character = Character::builder()
+			.with_attributes(attributes())
+			.with_skills(skills())
+			.with_specialties(Skill::Occult, vec!["Ghosts".into(), "Rituals".into()])
+			.with_specialties(Skill::Stealth, vec!["Shadows".into()])
+			.with_merits([(Merit::Resources, 3)])
+			.with_abilities([(Merit::Giant.into(), 3)])
+			.build();
+
+		assert_eq!(
+			character.creation_violations(),
+			vec![CreationViolation::MeritDots {
+				spent: 6,
+				expected: MERIT_DOTS
+			}]
+		);
+	}
+
+	#[test]
+	fn vampire() {
+		let character = Character::builder()
+			.with_splat(Vampire::new(Clan::Daeva, None, None).with_attr_bonus(Attribute::Dexterity))
+			.with_attributes(Attributes {
+				strength: 3,
+				..attributes()
+			})
+			.with_skills(skills())
+			.with_abilities([
+				(Discipline::Celerity.into(), 1),
+				(Discipline::Dominate.into(), 2),
+			])
+			.with_st(2)
+			.build();
+
+		assert_eq!(
+			character.creation_violations(),
+			vec![
+				CreationViolation::AttributePriorities { spent: [5, 5, 3] },
+				CreationViolation::Specialties {
+					count: 0,
+					expected: SPECIALTIES
+				},
+				CreationViolation::MeritDots {
+					spent: MERIT_DOTS_PER_TOLERANCE,
+					expected: MERIT_DOTS
+				},
+				CreationViolation::FavoredAbilityDots {
+					spent: 1,
+					required: 2
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn mage() {
+		let character = Character::builder()
+			.with_splat(Mage::new(Path::Acanthus))
+			.with_st(5)
+			.with_abilities([
+				(Arcanum::Time.into(), 4),
+				(Arcanum::Forces.into(), 1),
+				(Arcanum::Mind.into(), 1),
+			])
+			.build();
+
+		let violations = character.creation_violations();
+		assert!(violations.contains(&CreationViolation::ForbiddenAbility {
+			ability: Arcanum::Forces.into()
+		}));
+		assert!(
+			violations.contains(&CreationViolation::AbilityAboveMaximum {
+				ability: Arcanum::Time.into(),
+				rating: 4,
+				max: 3
+			})
+		);
+		assert!(!violations.iter().any(|violation| matches!(
+			violation,
+			CreationViolation::AbilityDots { .. } | CreationViolation::FavoredAbilityDots { .. }
+		)));
+	}
+}
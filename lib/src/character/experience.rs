@@ -0,0 +1,512 @@
+use chrono::NaiveDate;
+use cofd_schema::template::{Payment, PurchaseKind};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{Character, modifier::Dependency};
+use crate::{
+	prelude::{Attribute, Skill},
+	splat::{Merit, Splat, SplatTrait, ability::Ability},
+};
+
+/// Beats needed for one Experience.
+pub const BEATS_PER_EXPERIENCE: u16 = 5;
+
+/// A trait Experience can be spent on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TraitRef {
+	Attribute(Attribute),
+	Skill(Skill),
+	Specialty(Skill, String),
+	Ability(Ability),
+	Merit(Merit),
+	SupernaturalTolerance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LedgerChange {
+	Beats {
+		beats: u16,
+		#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+		alternate: bool,
+	},
+	Purchase {
+		#[serde(rename = "trait")]
+		trait_: TraitRef,
+		from: u16,
+		to: u16,
+		experience: u16,
+		#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+		alternate: bool,
+	},
+	/// Reverses the entry at the given index.
+	Undo(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+	pub date: NaiveDate,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub session: Option<u16>,
+	#[serde(default, skip_serializing_if = "String::is_empty")]
+	pub reason: String,
+	pub change: LedgerChange,
+}
+
+impl LedgerEntry {
+	pub fn new(date: NaiveDate, reason: impl Into<String>, change: LedgerChange) -> Self {
+		Self {
+			date,
+			session: None,
+			reason: reason.into(),
+			change,
+		}
+	}
+
+	#[must_use]
+	pub fn with_session(mut self, session: u16) -> Self {
+		self.session = Some(session);
+		self
+	}
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LedgerError {
+	#[error("No ledger entry #{_0}")]
+	NoSuchEntry(usize),
+	#[error("Ledger entry #{_0} was already undone")]
+	AlreadyUndone(usize),
+	#[error("Ledger entry #{_0} is itself an undo")]
+	UndoOfUndo(usize),
+	#[error("Ledger entry #{_0} was followed by another purchase of the same trait")]
+	Superseded(usize),
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PurchaseError {
+	#[error("{_0:?} can't be bought with Experience by this character")]
+	NotPurchasable(TraitRef),
+	#[error("The new rating {to} isn't above the current rating {from}")]
+	NotAnIncrease { from: u16, to: u16 },
+	#[error("The new rating {rating} is above the maximum of {max}")]
+	AboveMaximum { rating: u16, max: u16 },
+	#[error("Costs {cost} Experience, only {available} available")]
+	InsufficientExperience {
+		cost: u16,
+		available: u16,
+		alternate: bool,
+	},
+}
+
+/// Append-only record of Beats earned and Experience spent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger(Vec<LedgerEntry>);
+
+impl Ledger {
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	pub fn entries(&self) -> &[LedgerEntry] {
+		&self.0
+	}
+
+	/// Appends an entry, returning its index.
+	pub fn push(&mut self, entry: LedgerEntry) -> usize {
+		self.0.push(entry);
+		self.0.len() - 1
+	}
+
+	pub fn is_undone(&self, index: usize) -> bool {
+		self.0
+			.iter()
+			.any(|entry| entry.change == LedgerChange::Undo(index))
+	}
+
+	/// Appends an entry reversing the entry at `index`.
+	///
+	/// # Errors
+	/// If there is no such entry, it was already undone or is an undo itself.
+	pub fn undo(
+		&mut self,
+		index: usize,
+		date: NaiveDate,
+		reason: impl Into<String>,
+	) -> Result<usize, LedgerError> {
+		match self.0.get(index) {
+			None => Err(LedgerError::NoSuchEntry(index)),
+			Some(LedgerEntry {
+				change: LedgerChange::Undo(_),
+				..
+			}) => Err(LedgerError::UndoOfUndo(index)),
+			Some(_) if self.is_undone(index) => Err(LedgerError::AlreadyUndone(index)),
+			Some(_) => Ok(self.push(LedgerEntry::new(date, reason, LedgerChange::Undo(index)))),
+		}
+	}
+
+	/// Entries that are in effect: neither undone nor undos.
+	pub fn active(&self) -> impl Iterator<Item = (usize, &LedgerEntry)> {
+		self.0.iter().enumerate().filter(|(index, entry)| {
+			!matches!(entry.change, LedgerChange::Undo(_)) && !self.is_undone(*index)
+		})
+	}
+
+	pub fn earned_beats(&self, alternate: bool) -> u32 {
+		self.active()
+			.filter_map(|(_, entry)| match entry.change {
+				LedgerChange::Beats {
+					beats,
+					alternate: a,
+				} if a == alternate => Some(u32::from(beats)),
+				_ => None,
+			})
+			.sum()
+	}
+
+	pub fn spent_experience(&self, alternate: bool) -> u32 {
+		self.active()
+			.filter_map(|(_, entry)| match entry.change {
+				LedgerChange::Purchase {
+					experience,
+					alternate: a,
+					..
+				} if a == alternate => Some(u32::from(experience)),
+				_ => None,
+			})
+			.sum()
+	}
+}
+
+impl Character {
+	fn available_beats_of(&self, opening: u16, alternate: bool) -> u16 {
+		let spent = self.ledger.spent_experience(alternate) * u32::from(BEATS_PER_EXPERIENCE);
+		let beats =
+			(u32::from(opening) + self.ledger.earned_beats(alternate)).saturating_sub(spent);
+		u16::try_from(beats).unwrap_or(u16::MAX)
+	}
+
+	/// Unspent Beats, counting the untracked `beats` and the ledger.
+	pub fn available_beats(&self) -> u16 {
+		self.available_beats_of(self.beats, false)
+	}
+
+	pub fn available_alternate_beats(&self) -> u16 {
+		self.available_beats_of(self.alternate_beats, true)
+	}
+
+	pub fn award_beats(&mut self, date: NaiveDate, reason: impl Into<String>, beats: u16) -> usize {
+		self.ledger.push(LedgerEntry::new(
+			date,
+			reason,
+			LedgerChange::Beats {
+				beats,
+				alternate: false,
+			},
+		))
+	}
+
+	pub fn award_alternate_beats(
+		&mut self,
+		date: NaiveDate,
+		reason: impl Into<String>,
+		beats: u16,
+	) -> usize {
+		self.ledger.push(LedgerEntry::new(
+			date,
+			reason,
+			LedgerChange::Beats {
+				beats,
+				alternate: true,
+			},
+		))
+	}
+}
+
+impl Character {
+	/// The trait's rating, before any modifiers.
+	pub fn trait_rating(&self, trait_: &TraitRef) -> u16 {
+		match trait_ {
+			TraitRef::Attribute(attr) => *self._attributes.get(attr),
+			TraitRef::Skill(skill) => self.skills.get(*skill),
+			TraitRef::Specialty(skill, name) => u16::from(
+				self.specialties
+					.get(skill)
+					.is_some_and(|specialties| specialties.contains(name)),
+			),
+			TraitRef::Ability(ability) => self.abilities.get(ability).copied().unwrap_or(0),
+			TraitRef::Merit(merit) => self
+				.merits
+				.iter()
+				.find(|(m, _)| m == merit)
+				.map_or(0, |(_, rating)| *rating),
+			TraitRef::SupernaturalTolerance => self.power,
+		}
+	}
+
+	fn set_trait_rating(&mut self, trait_: &TraitRef, rating: u16) {
+		match trait_ {
+			TraitRef::Attribute(attr) => *self._attributes.get_mut(attr) = rating,
+			TraitRef::Skill(skill) => *self.skills.get_mut(*skill) = rating,
+			TraitRef::Specialty(skill, name) => {
+				let specialties = self.specialties.entry(*skill).or_default();
+				specialties.retain(|s| s != name);
+				if rating > 0 {
+					specialties.push(name.clone());
+				}
+			}
+			TraitRef::Ability(ability) => {
+				if rating > 0 {
+					self.abilities.insert(ability.clone(), rating);
+				} else {
+					self.abilities.remove(ability);
+				}
+				self.invalidate(Dependency::Ability(ability.clone()));
+			}
+			TraitRef::Merit(merit) => {
+				if let Some(i) = self.merits.iter().position(|(m, _)| m == merit) {
+					if rating > 0 {
+						self.merits[i].1 = rating;
+					} else {
+						self.merits.remove(i);
+					}
+				} else if rating > 0 {
+					self.merits.push((merit.clone(), rating));
+				}
+				self.invalidate(Dependency::Merits);
+			}
+			TraitRef::SupernaturalTolerance => self.power = rating,
+		}
+	}
+
+	fn purchase_kind(&self, trait_: &TraitRef) -> Option<PurchaseKind> {
+		Some(match trait_ {
+			TraitRef::Attribute(_) => PurchaseKind::Attribute,
+			TraitRef::Skill(_) => PurchaseKind::Skill,
+			TraitRef::Specialty(..) => PurchaseKind::Specialty,
+			TraitRef::Merit(_) | TraitRef::Ability(Ability::Merit(_)) => PurchaseKind::Merit,
+			TraitRef::Ability(ability) => {
+				let own = matches!(
+					(&self.splat, ability),
+					(Splat::Vampire(_), Ability::Discipline(_))
+						| (Splat::Werewolf(_), Ability::Renown(_))
+						| (Splat::Mage(_), Ability::Arcanum(_))
+						| (Splat::Bound(_), Ability::Haunt(_))
+				);
+				if !own {
+					return None;
+				}
+
+				PurchaseKind::Ability {
+					affinity: self.splat.is_affinity(ability),
+				}
+			}
+			TraitRef::SupernaturalTolerance => PurchaseKind::SupernaturalTolerance,
+		})
+	}
+
+	/// Buys `trait_` up to `rating` with Experience, recording it in the [`Ledger`].
+	///
+	/// Returns the index of the new ledger entry.
+	///
+	/// # Errors
+	/// If the character's template can't buy the trait, the rating isn't an increase
+	/// or is out of range, or there isn't enough Experience in the right pool.
+	pub fn purchase(
+		&mut self,
+		date: NaiveDate,
+		reason: impl Into<String>,
+		trait_: TraitRef,
+		rating: u16,
+	) -> Result<usize, PurchaseError> {
+		let cost = self
+			.purchase_kind(&trait_)
+			.and_then(|kind| self.splat.experience_cost(kind))
+			.ok_or_else(|| PurchaseError::NotPurchasable(trait_.clone()))?;
+
+		let from = self.trait_rating(&trait_);
+		if rating <= from {
+			return Err(PurchaseError::NotAnIncrease { from, to: rating });
+		}
+		let max = self.max_rating(&trait_);
+		if rating > max {
+			return Err(PurchaseError::AboveMaximum { rating, max });
+		}
+
+		let experience = cost.cost(from, rating);
+		let alternate = match cost.payment {
+			Payment::Experience => false,
+			Payment::AlternateExperience => true,
+			Payment::Either => self.alternate_experience() >= experience,
+		};
+		let available = if alternate {
+			self.alternate_experience()
+		} else {
+			self.experience()
+		};
+		if available < experience {
+			return Err(PurchaseError::InsufficientExperience {
+				cost: experience,
+				available,
+				alternate,
+			});
+		}
+
+		self.set_trait_rating(&trait_, rating);
+		Ok(self.ledger.push(LedgerEntry::new(
+			date,
+			reason,
+			LedgerChange::Purchase {
+				trait_,
+				from,
+				to: rating,
+				experience,
+				alternate,
+			},
+		)))
+	}
+
+	/// Undoes a ledger entry, restoring the trait's previous rating if it was a purchase.
+	///
+	/// # Errors
+	/// If the entry can't be undone, or a later purchase of the same trait would have to
+	/// be undone first.
+	pub fn undo(
+		&mut self,
+		index: usize,
+		date: NaiveDate,
+		reason: impl Into<String>,
+	) -> Result<usize, LedgerError> {
+		let purchase = match self.ledger.entries().get(index) {
+			Some(LedgerEntry {
+				change: LedgerChange::Purchase { trait_, from, .. },
+				..
+			}) => Some((trait_.clone(), *from)),
+			_ => None,
+		};
+
+		if let Some((trait_, _)) = &purchase {
+			let superseded = self.ledger.active().any(|(i, entry)| {
+				i > index
+					&& matches!(&entry.change, LedgerChange::Purchase { trait_: t, .. } if t == trait_)
+			});
+			if superseded {
+				return Err(LedgerError::Superseded(index));
+			}
+		}
+
+		let undo = self.ledger.undo(index, date, reason)?;
+		if let Some((trait_, from)) = purchase {
+			self.set_trait_rating(&trait_, from);
+		}
+
+		Ok(undo)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::splat::{
+		mage::Arcanum,
+		vampire::{Discipline, Vampire},
+	};
+
+	#[test]
+	fn ledger() {
+		let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+		let mut character = Character {
+			beats: 3,
+			..Default::default()
+		};
+
+		character.award_beats(date, "Resolved a Condition", 1);
+		character.ledger.push(
+			LedgerEntry::new(
+				date,
+				"Aspiration",
+				LedgerChange::Beats {
+					beats: 6,
+					alternate: false,
+				},
+			)
+			.with_session(4),
+		);
+		let purchase = character.ledger.push(LedgerEntry::new(
+			date,
+			"",
+			LedgerChange::Purchase {
+				trait_: TraitRef::Skill(Skill::Brawl),
+				from: 0,
+				to: 1,
+				experience: 2,
+				alternate: false,
+			},
+		));
+		assert_eq!(character.available_beats(), 0);
+		assert_eq!(character.experience(), 0);
+
+		let undo = character.ledger.undo(purchase, date, "Misclick").unwrap();
+		assert_eq!(character.experience(), 2);
+		assert_eq!(
+			character.ledger.undo(purchase, date, ""),
+			Err(LedgerError::AlreadyUndone(purchase))
+		);
+		assert_eq!(
+			character.ledger.undo(undo, date, ""),
+			Err(LedgerError::UndoOfUndo(undo))
+		);
+
+		let ron = ron::to_string(&character.ledger).unwrap();
+		assert_eq!(ron::from_str::<Ledger>(&ron).unwrap(), character.ledger);
+	}
+
+	#[test]
+	fn purchase() {
+		let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+		let mut character = Character::builder().with_splat(Vampire::default()).build();
+		character.beats = 5 * 10;
+		character.alternate_beats = 5 * 3;
+
+		let celerity = TraitRef::Ability(Discipline::Celerity.into());
+		character
+			.purchase(date, "Celerity", celerity.clone(), 1)
+			.unwrap();
+		assert_eq!(character.alternate_experience(), 0);
+		assert_eq!(character.trait_rating(&celerity), 1);
+
+		let entry = character
+			.purchase(
+				date,
+				"Strength",
+				TraitRef::Attribute(Attribute::Strength),
+				2,
+			)
+			.unwrap();
+		assert_eq!(character.experience(), 6);
+
+		assert_eq!(
+			character.purchase(date, "", TraitRef::Ability(Discipline::Dominate.into()), 2),
+			Err(PurchaseError::InsufficientExperience {
+				cost: 8,
+				available: 6,
+				alternate: false
+			})
+		);
+		assert_eq!(
+			character.purchase(date, "", TraitRef::Ability(Arcanum::Mind.into()), 1),
+			Err(PurchaseError::NotPurchasable(TraitRef::Ability(
+				Arcanum::Mind.into()
+			)))
+		);
+		assert_eq!(
+			character.purchase(date, "", TraitRef::Skill(Skill::Brawl), 6),
+			Err(PurchaseError::AboveMaximum { rating: 6, max: 5 })
+		);
+
+		character.undo(entry, date, "Wrong Attribute").unwrap();
+		assert_eq!(character.ledger.entries()[entry].reason, "Strength");
+		assert_eq!(character.experience(), 10);
+		assert_eq!(character.base_attributes().strength, 1);
+	}
+}
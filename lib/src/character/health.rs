@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Character, Damage, Wound};
+use crate::splat::SplatTrait;
+
+/// Roughly three seconds of action.
+pub const TURN: Duration = Duration::from_secs(3);
+
+/// Time it takes a mortal to heal a single box of each kind of damage.
+pub const BASHING_HEALING_TIME: Duration = Duration::from_mins(15);
+pub const LETHAL_HEALING_TIME: Duration = Duration::from_hours(48);
+pub const AGGRAVATED_HEALING_TIME: Duration = Duration::from_hours(24 * 7);
+
+/// Natural healing time of a single box of `wound`.
+pub fn natural_healing_time(wound: &Wound) -> Option<Duration> {
+	match wound {
+		Wound::None => None,
+		Wound::Bashing => Some(BASHING_HEALING_TIME),
+		Wound::Lethal => Some(LETHAL_HEALING_TIME),
+		Wound::Aggravated => Some(AGGRAVATED_HEALING_TIME),
+	}
+}
+
+/// Fuel that heals damage, e.g. Vitae: `fuel` points heal `boxes` boxes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuelHealing {
+	pub fuel: u16,
+	pub boxes: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthState {
+	Healthy,
+	Injured,
+	/// The Health track is full and the rightmost box holds bashing damage.
+	Unconscious,
+	/// The Health track is full of lethal and aggravated damage.
+	BleedingOut,
+	/// The Health track is full of aggravated damage.
+	Dead,
+}
+
+impl HealthState {
+	pub fn is_incapacitated(self) -> bool {
+		matches!(
+			self,
+			HealthState::Unconscious | HealthState::BleedingOut | HealthState::Dead
+		)
+	}
+}
+
+impl Damage {
+	/// Marks `amount` boxes of `wound` on a track of `max` boxes.
+	///
+	/// Once the track is full, every further box upgrades the leftmost bashing box to
+	/// lethal, or if there is none the leftmost lethal box to aggravated.
+	/// Aggravated damage upgrades straight to aggravated.
+	///
+	/// Healing time carried over towards the rightmost box is lost if that box changes.
+	pub fn apply(&mut self, wound: &Wound, amount: u16, max: u16) {
+		if max == 0 || amount == 0 || *wound == Wound::None {
+			return;
+		}
+		let rightmost = self.rightmost();
+
+		for _ in 0..amount {
+			if self.sum() < max {
+				self.inc(wound);
+			} else if self.bashing > 0 {
+				self.bashing -= 1;
+				match wound {
+					Wound::Aggravated => self.aggravated += 1,
+					_ => self.lethal += 1,
+				}
+			} else if self.lethal > 0 {
+				self.lethal -= 1;
+				self.aggravated += 1;
+			} else {
+				break;
+			}
+		}
+
+		// Upgrades can leave the track over-full if `max` shrank.
+		while self.sum() > max {
+			if self.bashing > 0 {
+				self.bashing -= 1;
+			} else if self.lethal > 0 {
+				self.lethal -= 1;
+			} else {
+				self.aggravated -= 1;
+			}
+		}
+
+		if self.rightmost() != rightmost {
+			self.healing = Duration::ZERO;
+		}
+	}
+
+	/// The least severe wound, which heals first.
+	pub fn rightmost(&self) -> Wound {
+		if self.bashing > 0 {
+			Wound::Bashing
+		} else if self.lethal > 0 {
+			Wound::Lethal
+		} else if self.aggravated > 0 {
+			Wound::Aggravated
+		} else {
+			Wound::None
+		}
+	}
+
+	pub fn state(&self, max: u16) -> HealthState {
+		if self.sum() == 0 {
+			HealthState::Healthy
+		} else if self.sum() < max {
+			HealthState::Injured
+		} else if self.aggravated >= max {
+			HealthState::Dead
+		} else if self.bashing > 0 {
+			HealthState::Unconscious
+		} else {
+			HealthState::BleedingOut
+		}
+	}
+
+	/// Heals boxes from the right, each taking `healing_time` to heal.
+	///
+	/// Time that isn't enough to heal the next box carries over to the next call.
+	/// Returns the number of boxes healed.
+	pub fn heal(
+		&mut self,
+		elapsed: Duration,
+		healing_time: impl Fn(&Wound) -> Option<Duration>,
+	) -> u16 {
+		let mut healed = 0;
+		self.healing += elapsed;
+
+		loop {
+			let wound = self.rightmost();
+			let Some(time) = healing_time(&wound) else {
+				break;
+			};
+
+			if self.healing < time {
+				return healed;
+			}
+
+			self.healing -= time;
+			self.dec(&wound);
+			healed += 1;
+		}
+
+		self.healing = Duration::ZERO;
+		healed
+	}
+}
+
+impl Character {
+	/// Applies damage, returning the resulting state.
+	pub fn apply_damage(&mut self, wound: &Wound, amount: u16) -> HealthState {
+		let max = self.max_health();
+		self.health.apply(wound, amount, max);
+		self.health_state()
+	}
+
+	/// Heals naturally over `elapsed`, returning the number of boxes healed.
+	pub fn heal(&mut self, elapsed: Duration) -> u16 {
+		let splat = &self.splat;
+		self.health.heal(elapsed, |wound| splat.healing_time(wound))
+	}
+
+	/// Spends fuel to heal the rightmost wound, if the template allows it.
+	///
+	/// Healing time banked towards the healed box is lost.
+	/// Returns the number of boxes healed, 0 if there wasn't enough fuel.
+	pub fn heal_with_fuel(&mut self) -> u16 {
+		let wound = self.health.rightmost();
+		let Some(FuelHealing { fuel, boxes }) = self.splat.fuel_healing(&wound) else {
+			return 0;
+		};
+		if self.fuel < fuel {
+			return 0;
+		}
+
+		self.fuel -= fuel;
+		let healed = boxes.min(self.health.get(&wound));
+		for _ in 0..healed {
+			self.health.dec(&wound);
+		}
+		if healed > 0 {
+			self.health.healing = Duration::ZERO;
+		}
+		healed
+	}
+
+	pub fn health_state(&self) -> HealthState {
+		self.health.state(self.max_health())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::splat::{vampire::Vampire, werewolf::Werewolf};
+
+	#[test]
+	fn overflow() {
+		let mut damage = Damage::new(2, 1, 0);
+		damage.apply(&Wound::Bashing, 1, 4);
+		assert_eq!(damage, Damage::new(3, 1, 0));
+
+		damage.apply(&Wound::Lethal, 1, 4);
+		assert_eq!(damage, Damage::new(2, 2, 0));
+		assert_eq!(damage.state(4), HealthState::Unconscious);
+
+		damage.apply(&Wound::Bashing, 2, 4);
+		assert_eq!(damage.state(4), HealthState::BleedingOut);
+
+		damage.apply(&Wound::Aggravated, 1, 4);
+		assert_eq!(damage, Damage::new(0, 3, 1));
+
+		damage.apply(&Wound::Bashing, 3, 4);
+		assert_eq!(damage.state(4), HealthState::Dead);
+	}
+
+	#[test]
+	fn healing() {
+		let mut character = Character::default();
+		character.apply_damage(&Wound::Lethal, 1);
+		character.apply_damage(&Wound::Bashing, 2);
+		assert_eq!(character.health_state(), HealthState::Injured);
+
+		assert_eq!(character.heal(Duration::from_mins(20)), 1);
+		assert_eq!(character.heal(Duration::from_mins(10)), 1);
+		assert_eq!(character.heal(Duration::from_hours(47)), 0);
+		assert_eq!(character.heal(Duration::from_hours(1)), 1);
+		assert_eq!(character.health_state(), HealthState::Healthy);
+
+		// Time spent healing a lethal box doesn't heal bashing damage taken later.
+		character.apply_damage(&Wound::Lethal, 1);
+		assert_eq!(character.heal(Duration::from_hours(47)), 0);
+		character.apply_damage(&Wound::Bashing, 1);
+		assert_eq!(character.heal(Duration::from_mins(10)), 0);
+		assert_eq!(character.heal(Duration::from_mins(5)), 1);
+
+		let mut werewolf = Character::builder().with_splat(Werewolf::default()).build();
+		werewolf.apply_damage(&Wound::Lethal, 1);
+		werewolf.apply_damage(&Wound::Bashing, 2);
+		assert_eq!(werewolf.heal(TURN * 2 + BASHING_HEALING_TIME), 3);
+
+		let mut vampire = Character::builder()
+			.with_splat(Vampire::default())
+			.with_fuel(2)
+			.build();
+		vampire.apply_damage(&Wound::Bashing, 3);
+		assert_eq!(vampire.heal(Duration::from_hours(1)), 0);
+		vampire.health.healing = Duration::from_mins(10);
+		vampire.apply_damage(&Wound::None, 1);
+		vampire.apply_damage(&Wound::Lethal, 0);
+		assert_eq!(vampire.health.healing, Duration::from_mins(10));
+		assert_eq!(vampire.heal_with_fuel(), 2);
+		assert_eq!(vampire.health.healing, Duration::ZERO);
+		assert_eq!(vampire.heal_with_fuel(), 1);
+		assert_eq!(vampire.heal_with_fuel(), 0);
+		assert_eq!(vampire.fuel, 0);
+	}
+}
@@ -0,0 +1,362 @@
+use cofd_schema::{
+	dot_range::DotRange,
+	library::Library,
+	prerequisites::PrerequisiteSubject,
+	template::{MAX_TOLERANCE, MORTAL_TRAIT_MAX, TraitLimits},
+};
+use cofd_util::VariantName;
+use thiserror::Error;
+
+use super::{Character, TraitRef, modifier::Dependency};
+use crate::{
+	prelude::{Attribute, Skill},
+	splat::{Merit, ability::Ability},
+};
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuelError {
+	#[error("Only {available} fuel left")]
+	NotEnough { available: u16 },
+	#[error("Only {max} fuel can be spent per turn")]
+	AbovePerTurn { max: u16 },
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MeritRatingError {
+	#[error("No merit #{_0}")]
+	NoMerit(usize),
+	/// `name` is the display name of the merit's item.
+	#[error("{name} can't be rated {rating}, only {allowed}")]
+	Illegal {
+		index: usize,
+		merit: Merit,
+		name: String,
+		rating: u16,
+		allowed: DotRange,
+	},
+	/// `limit` is the display name of the merit capping the rating.
+	#[error("{name} can't be rated above its {limit} rating of {max}")]
+	AboveMerit {
+		index: usize,
+		merit: Merit,
+		name: String,
+		rating: u16,
+		limit: String,
+		max: u16,
+	},
+}
+
+/// Checks the merit's rating against its item in `library`, and against the merit
+/// capping it, if any.
+///
+/// 0 is always legal, as merits are added without dots, and so is any rating of a merit
+/// without an item or a dot rating.
+fn check_merit_rating(
+	character: &Character,
+	index: usize,
+	merit: &Merit,
+	rating: u16,
+	library: &Library,
+) -> Result<(), MeritRatingError> {
+	match merit
+		.item(library)
+		.and_then(|item| item.inner.dot_rating.as_ref())
+	{
+		Some(allowed)
+			if rating != 0
+				&& !u8::try_from(rating).is_ok_and(|rating| allowed.contains(rating)) =>
+		{
+			Err(MeritRatingError::Illegal {
+				index,
+				merit: merit.clone(),
+				name: merit.display_name(library),
+				rating,
+				allowed: allowed.clone(),
+			})
+		}
+		_ => match merit.capped_by() {
+			Some(limit) if rating > character.merit_rating(limit.name()) => {
+				Err(MeritRatingError::AboveMerit {
+					index,
+					merit: merit.clone(),
+					name: merit.display_name(library),
+					rating,
+					limit: limit.display_name(library),
+					max: character.merit_rating(limit.name()),
+				})
+			}
+			_ => Ok(()),
+		},
+	}
+}
+
+impl Character {
+	/// Limits set by the character's template and Supernatural Tolerance.
+	pub fn limits(&self) -> TraitLimits {
+		self.splat.limits(self.power)
+	}
+
+	/// Highest rating the trait can have.
+	pub fn max_rating(&self, trait_: &TraitRef) -> u16 {
+		match trait_ {
+			TraitRef::Attribute(_) | TraitRef::Skill(_) => self.limits().trait_max,
+			TraitRef::Specialty(..) => 1,
+			TraitRef::Ability(Ability::Merit(_)) | TraitRef::Merit(_) => MORTAL_TRAIT_MAX,
+			TraitRef::Ability(_) => self.limits().ability_max,
+			TraitRef::SupernaturalTolerance => {
+				if self.splat.supernatural_tolerance().is_some() {
+					MAX_TOLERANCE
+				} else {
+					0
+				}
+			}
+		}
+	}
+
+	/// Sets the Attribute's rating, capped at the character's trait maximum.
+	pub fn set_attribute(&mut self, attribute: &Attribute, rating: u16) {
+		*self._attributes.get_mut(attribute) = rating.clamp(1, self.limits().trait_max);
+	}
+
+	/// Sets the Skill's rating, capped at the character's trait maximum.
+	pub fn set_skill(&mut self, skill: Skill, rating: u16) {
+		*self.skills.get_mut(skill) = rating.min(self.limits().trait_max);
+	}
+
+	/// Sets the ability's rating, capped at its maximum.
+	pub fn set_ability(&mut self, ability: Ability, rating: u16) {
+		let rating = rating.min(self.max_rating(&TraitRef::Ability(ability.clone())));
+		self.abilities.insert(ability.clone(), rating);
+		self.invalidate(Dependency::Ability(ability));
+	}
+
+	/// Sets the rating of the merit at `i`, if its item in `library` allows it.
+	///
+	/// # Errors
+	/// If there's no merit at `i`, or it can't have the rating, in which case nothing
+	/// changes.
+	pub fn set_merit_rating(
+		&mut self,
+		i: usize,
+		rating: u16,
+		library: &Library,
+	) -> Result<(), MeritRatingError> {
+		let (merit, _) = self.merits.get(i).ok_or(MeritRatingError::NoMerit(i))?;
+		check_merit_rating(self, i, merit, rating, library)?;
+
+		self.merits[i].1 = rating;
+		self.invalidate(Dependency::Merits);
+		Ok(())
+	}
+
+	/// Checks a loaded character against `library`, e.g. after deserializing it from a
+	/// file edited by hand.
+	///
+	/// # Errors
+	/// Every merit with a rating its item doesn't allow.
+	pub fn validate(&self, library: &Library) -> Result<(), Vec<MeritRatingError>> {
+		let errors = self.merit_rating_errors(library);
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Merits with ratings their items in `library` don't allow, e.g. in a character loaded
+	/// from a file edited by hand.
+	pub fn merit_rating_errors(&self, library: &Library) -> Vec<MeritRatingError> {
+		self.merits
+			.iter()
+			.enumerate()
+			.filter_map(|(i, (merit, rating))| {
+				check_merit_rating(self, i, merit, *rating, library).err()
+			})
+			.collect()
+	}
+
+	/// Sets Supernatural Tolerance, capping fuel at the new maximum.
+	///
+	/// Traits above the new maximums are kept, as they were legal when they were bought.
+	pub fn set_power(&mut self, power: u16) {
+		self.power = power.min(self.max_rating(&TraitRef::SupernaturalTolerance));
+		self.fuel = self.fuel.min(self.max_fuel());
+	}
+
+	/// Spends `amount` fuel in a single turn.
+	///
+	/// # Errors
+	/// If there isn't enough fuel, or more than can be spent in a turn,
+	/// in which case nothing is spent.
+	pub fn spend_fuel(&mut self, amount: u16) -> Result<(), FuelError> {
+		let max = self.limits().fuel_per_turn;
+		if amount > max {
+			return Err(FuelError::AbovePerTurn { max });
+		}
+
+		self.fuel = self.fuel.checked_sub(amount).ok_or(FuelError::NotEnough {
+			available: self.fuel,
+		})?;
+		Ok(())
+	}
+
+	/// Caps every trait at its maximum.
+	pub(super) fn clamp_traits(&mut self) {
+		let trait_max = self.limits().trait_max;
+
+		for attribute in <Attribute as strum::VariantArray>::VARIANTS {
+			let rating = self._attributes.get_mut(attribute);
+			*rating = (*rating).min(trait_max);
+		}
+		for skill in <Skill as strum::VariantArray>::VARIANTS {
+			let rating = self.skills.get_mut(*skill);
+			*rating = (*rating).min(trait_max);
+		}
+
+		let ability_max = self.limits().ability_max;
+		for (ability, rating) in &mut self.abilities {
+			let max = if matches!(ability, Ability::Merit(_)) {
+				MORTAL_TRAIT_MAX
+			} else {
+				ability_max
+			};
+			*rating = (*rating).min(max);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		character::Attributes,
+		splat::vampire::{Discipline, Vampire},
+	};
+
+	#[test]
+	fn limits() {
+		let mortal = Character::builder()
+			.with_attributes(Attributes {
+				strength: 7,
+				..Default::default()
+			})
+			.build();
+		assert_eq!(mortal.base_attributes().strength, 5);
+		assert_eq!(mortal.max_rating(&TraitRef::SupernaturalTolerance), 0);
+
+		let mut vampire = Character::builder()
+			.with_splat(Vampire::default())
+			.with_st(7)
+			.with_abilities([(Discipline::Vigor.into(), 8)])
+			.build();
+		assert_eq!(vampire.max_fuel(), 25);
+		assert_eq!(vampire.abilities[&Discipline::Vigor.into()], 7);
+
+		vampire.set_attribute(&Attribute::Strength, 9);
+		assert_eq!(vampire.base_attributes().strength, 7);
+
+		assert_eq!(
+			vampire.spend_fuel(8),
+			Err(FuelError::AbovePerTurn { max: 7 })
+		);
+		vampire.spend_fuel(7).unwrap();
+
+		vampire.set_power(1);
+		assert_eq!(vampire.fuel, 10);
+		assert_eq!(vampire.base_attributes().strength, 7);
+
+		vampire.fuel = 0;
+		assert_eq!(
+			vampire.spend_fuel(1),
+			Err(FuelError::NotEnough { available: 0 })
+		);
+	}
+
+	#[test]
+	fn merit_ratings() {
+		let mut library = Library::new();
+		library.add_book(ron::from_str(include_str!("../../data/merits_universal.ron")).unwrap());
+
+		let character = Character::builder()
+			.with_merits([(Merit::FastReflexes, 4), (Merit::Giant, 3)])
+			.build();
+		let mut character: Character = ron::from_str(&ron::to_string(&character).unwrap()).unwrap();
+		let errors = character.validate(&library).unwrap_err();
+		assert_eq!(
+			errors,
+			vec![MeritRatingError::Illegal {
+				index: 0,
+				merit: Merit::FastReflexes,
+				name: "Fast Reflexes".to_owned(),
+				rating: 4,
+				allowed: DotRange::Range(1..=3),
+			}]
+		);
+		assert_eq!(
+			errors[0].to_string(),
+			"Fast Reflexes can't be rated 4, only • to •••"
+		);
+
+		assert!(character.set_merit_rating(1, 2, &library).is_err());
+		assert_eq!(character.merits[1].1, 3);
+		assert_eq!(
+			character.set_merit_rating(2, 1, &library),
+			Err(MeritRatingError::NoMerit(2))
+		);
+
+		character.set_merit_rating(0, 2, &library).unwrap();
+		character.add_merit(Merit::Custom("Homebrew".to_owned()));
+		character.set_merit_rating(2, 7, &library).unwrap();
+		assert_eq!(character.validate(&library), Ok(()));
+
+		character.add_merit(Merit::SafePlace(String::new()));
+		character.set_merit_rating(3, 2, &library).unwrap();
+		character.add_merit(Merit::LibraryAdvanced(vec![]));
+		assert_eq!(
+			character.set_merit_rating(4, 3, &library),
+			Err(MeritRatingError::AboveMerit {
+				index: 4,
+				merit: Merit::LibraryAdvanced(vec![]),
+				name: "Library (Advanced)".to_owned(),
+				rating: 3,
+				limit: "Safe Place".to_owned(),
+				max: 2,
+			})
+		);
+		character.set_merit_rating(4, 2, &library).unwrap();
+
+		character.set_merit_rating(3, 1, &library).unwrap();
+		assert_eq!(character.merit_rating_errors(&library).len(), 1);
+	}
+
+	#[test]
+	fn unspecified_merit_rating() {
+		let mut library = Library::new();
+		library.add_book(
+			ron::from_str(
+				r#"(
+				info: (name: "Homebrew", id: Codex, hash: "0", publicationDate: "2024-01-01"),
+				merits: [
+					{ "name": "Fame", "reference": (Codex, 1) },
+					{ "name": "Resources", "reference": (Codex, 1), "dotRating": 0 },
+				],
+			)"#,
+			)
+			.unwrap(),
+		);
+
+		let mut character = Character::builder()
+			.with_merits([(Merit::Fame, 1), (Merit::Resources, 1)])
+			.build();
+
+		// Without a rating in the data, any rating is allowed.
+		character.set_merit_rating(0, 7, &library).unwrap();
+		assert!(matches!(
+			character.set_merit_rating(1, 1, &library),
+			Err(MeritRatingError::Illegal {
+				allowed: DotRange::Num(0),
+				..
+			})
+		));
+	}
+}
@@ -2,22 +2,39 @@ use std::{
 	cmp::min,
 	collections::HashMap,
 	ops::{Add, Sub},
+	sync::Arc,
+	time::Duration,
 };
 
 use cofd_schema::{
 	dice_pool::DicePool,
+	item::merit::MeritSubItem,
+	library::{Library, key as item_key},
 	prelude::{Attribute, Skill},
+	prerequisites::PrerequisiteSubject,
+	template::Template,
+	traits as schema_traits,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
 	prelude::VariantName,
-	splat::{ability::Ability, Merit, Splat},
+	splat::{ability::Ability, Merit, Splat, SplatTrait},
 };
 
+pub mod conditions;
+pub mod creation;
+pub mod experience;
+pub mod health;
+pub mod limits;
 pub mod modifier;
 // pub mod traits;
 
+pub use creation::CreationViolation;
+use experience::BEATS_PER_EXPERIENCE;
+pub use experience::{Ledger, LedgerEntry, TraitRef};
+pub use health::HealthState;
+pub use limits::{FuelError, MeritRatingError};
 use modifier::*;
 
 use crate::{dice_pool::DicePoolExt, traits::*};
@@ -47,8 +64,7 @@ pub struct CharacterBuilder {
 	abilities: HashMap<Ability, u16>,
 	power: u16,
 	fuel: Option<u16>,
-
-	flag: bool,
+	book_modifiers: Arc<BookModifiers>,
 }
 
 impl CharacterBuilder {
@@ -85,15 +101,12 @@ impl CharacterBuilder {
 	#[must_use]
 	pub fn with_abilities<const N: usize>(mut self, abilities: [(Ability, u16); N]) -> Self {
 		self.abilities = HashMap::from(abilities);
-
-		self.flag = true;
 		self
 	}
 
 	#[must_use]
 	pub fn with_merits<const N: usize>(mut self, merits: [(Merit, u16); N]) -> Self {
 		self.merits = Vec::from(merits);
-		self.flag = true;
 		self
 	}
 
@@ -109,6 +122,14 @@ impl CharacterBuilder {
 		self
 	}
 
+	/// Applies modifiers from the items of loaded books. The index is shared, as it's
+	/// usually the same for every character.
+	#[must_use]
+	pub fn with_book_modifiers(mut self, book_modifiers: Arc<BookModifiers>) -> Self {
+		self.book_modifiers = book_modifiers;
+		self
+	}
+
 	#[must_use]
 	pub fn build(self) -> Character {
 		let power = if matches!(self.splat, Splat::Mortal(..)) {
@@ -128,21 +149,36 @@ impl CharacterBuilder {
 			abilities: self.abilities,
 			merits: self.merits,
 			specialties: self.specialties,
+			book_modifiers: self.book_modifiers,
 			..Default::default()
 		};
 
-		if self.flag {
-			character.calc_mod_map();
-		}
+		character.clamp_traits();
+		character.calc_mod_map();
 
 		character.fuel = self.fuel.unwrap_or_else(|| character.max_fuel());
 		character.willpower = character.max_willpower();
 
 		character
 	}
+
+	/// Builds the character, checking it against the character creation rules.
+	///
+	/// # Errors
+	/// Every [`CreationViolation`] found, if there are any.
+	pub fn try_build(self) -> Result<Character, Vec<CreationViolation>> {
+		let character = self.build();
+		let violations = character.creation_violations();
+
+		if violations.is_empty() {
+			Ok(character)
+		} else {
+			Err(violations)
+		}
+	}
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Wound {
 	#[default]
 	None,
@@ -185,6 +221,9 @@ pub struct Damage {
 	lethal: u16,
 	#[serde(skip_serializing_if = "is_zero")]
 	bashing: u16,
+	/// Time spent healing the rightmost wound.
+	#[serde(skip_serializing_if = "Duration::is_zero")]
+	healing: Duration,
 }
 
 impl Damage {
@@ -193,6 +232,7 @@ impl Damage {
 			aggravated,
 			lethal,
 			bashing,
+			healing: Duration::ZERO,
 		}
 	}
 
@@ -323,15 +363,23 @@ pub struct Character {
 	#[serde(skip_serializing_if = "is_five")]
 	pub base_size: u16,
 	base_armor: ArmorStruct,
+	/// Beats not tracked in the [`Ledger`].
 	pub beats: u16,
 	#[serde(skip_serializing_if = "is_zero")]
 	pub alternate_beats: u16,
+	#[serde(skip_serializing_if = "Ledger::is_empty")]
+	pub ledger: Ledger,
 
-	pub conditions: Vec<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub conditions: Vec<conditions::Condition>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub tilts: Vec<conditions::Tilt>,
 	pub aspirations: Vec<String>,
 
 	#[serde(skip)]
 	modifiers: Modifiers,
+	#[serde(skip)]
+	book_modifiers: Arc<BookModifiers>,
 }
 
 impl Character {
@@ -340,7 +388,8 @@ impl Character {
 	}
 
 	pub fn add_ability(&mut self, ability: Ability, val: u16) {
-		self.abilities.insert(ability, val);
+		self.abilities.insert(ability.clone(), val);
+		self.invalidate(Dependency::Ability(ability));
 	}
 
 	pub fn has_ability(&self, key: &Ability) -> bool {
@@ -348,33 +397,58 @@ impl Character {
 	}
 
 	pub fn remove_ability(&mut self, key: &Ability) -> Option<u16> {
-		self.abilities.remove(key)
+		let val = self.abilities.remove(key);
+		self.invalidate(Dependency::Ability(key.clone()));
+		val
 	}
 
 	pub fn get_ability_value(&self, key: &Ability) -> Option<&u16> {
 		self.abilities.get(key)
 	}
 
+	/// Modifiers are recalculated from scratch on the next read, so prefer
+	/// [`Character::add_ability`] to change a rating.
 	pub fn get_ability_value_mut(&mut self, key: &Ability) -> Option<&mut u16> {
+		self.modifiers.clear();
 		self.abilities.get_mut(key)
 	}
 
 	pub fn add_merit(&mut self, key: Merit) {
 		self.merits.push((key, 0));
+		self.invalidate(Dependency::Merits);
 	}
 
 	pub fn remove_merit(&mut self, i: usize) -> (Merit, u16) {
-		self.merits.remove(i)
+		let merit = self.merits.remove(i);
+		self.invalidate(Dependency::Merits);
+		merit
 	}
 
 	pub fn get_merit(&self, i: usize) -> Option<&(Merit, u16)> {
 		self.merits.get(i)
 	}
 
+	/// Modifiers are recalculated from scratch on the next read, so prefer
+	/// [`Character::set_merit_rating`] to change a rating.
 	pub fn get_merit_mut(&mut self, i: usize) -> Option<&mut (Merit, u16)> {
+		self.modifiers.clear();
 		self.merits.get_mut(i)
 	}
 
+	/// Maneuvers the character's merits grant, such as those of Fighting Styles, along
+	/// with the merit granting each.
+	pub fn maneuvers<'a>(&self, library: &'a Library) -> Vec<(&Merit, &'a MeritSubItem)> {
+		self.merits
+			.iter()
+			.flat_map(|(merit, rating)| {
+				merit
+					.maneuvers(*rating, self, library)
+					.into_iter()
+					.map(move |maneuver| (merit, maneuver))
+			})
+			.collect()
+	}
+
 	pub fn get_trait(&self, trait_: &Trait) -> u16 {
 		match trait_ {
 			Trait::DerivedTrait(dt) => match dt {
@@ -387,7 +461,8 @@ impl Character {
 				DerivedTrait::Size => self.size(),
 			},
 
-			Trait::Beats => self.beats,
+			Trait::Beats => self.available_beats(),
+			Trait::AlternateBeats => self.available_alternate_beats(),
 
 			// Trait::Armor(Some(armor)) => match armor {
 			// 	Armor::General => self.armor().general,
@@ -403,9 +478,33 @@ impl Character {
 		}
 	}
 
-	#[allow(clippy::too_many_lines)]
-	pub fn calc_mod_map(&self) {
-		self.modifiers.update(self);
+	/// Applies modifiers from the items of loaded books, recalculating every modifier.
+	pub fn set_book_modifiers(&mut self, book_modifiers: Arc<BookModifiers>) {
+		self.book_modifiers = book_modifiers;
+		self.calc_mod_map();
+	}
+
+	/// Recalculates every modifier.
+	///
+	/// Needed after changing the `splat`, `abilities`, `merits`, `conditions` or `tilts`
+	/// fields directly. Prefer [`Character::invalidate`] when it's known what changed.
+	pub fn calc_mod_map(&mut self) {
+		self.modifiers = Modifiers::new(self);
+	}
+
+	/// Recalculates the modifiers that depend on `dependency`, leaving the rest alone.
+	pub fn invalidate(&mut self, dependency: Dependency) {
+		if !self.modifiers.is_calculated() {
+			return;
+		}
+
+		// Moon Gifts scale with auspice Renown.
+		if matches!(dependency, Dependency::Ability(Ability::Renown(_))) {
+			self.invalidate(Dependency::Template);
+		}
+
+		let modifiers = Modifiers::collect_dependency(self, &dependency);
+		self.modifiers.replace(dependency, modifiers);
 	}
 
 	pub fn get_modifier(&self, target: impl Into<ModifierTarget>) -> i16 {
@@ -415,10 +514,20 @@ impl Character {
 	pub fn get_conditional_modifier(
 		&self,
 		target: impl Into<ModifierTarget>,
-		condition: impl Into<Condition>,
+		context: &RollContext,
 	) -> Option<i16> {
 		self.modifiers
-			.get_conditional_modifier(self, target, condition)
+			.get_conditional_modifier(self, target, context)
+	}
+
+	/// Every modifier on `target` that holds in `context`, with its source and whether
+	/// it applies under the stacking rules.
+	pub fn active_modifiers(
+		&self,
+		target: impl Into<ModifierTarget>,
+		context: &RollContext,
+	) -> Vec<ActiveModifier> {
+		self.modifiers.active(self, target, context)
 	}
 
 	pub fn get_pool(&self, target: impl Into<ModifierTarget>) -> Option<DicePool> {
@@ -428,9 +537,9 @@ impl Character {
 	pub fn get_conditional_pool(
 		&self,
 		target: impl Into<ModifierTarget>,
-		condition: impl Into<Condition>,
+		context: &RollContext,
 	) -> Option<DicePool> {
-		self.modifiers.get_conditional_pool(target, condition)
+		self.modifiers.get_conditional_pool(self, target, context)
 	}
 
 	pub fn attributes(&self) -> Attributes {
@@ -446,6 +555,8 @@ impl Character {
 			composure: self._modified(Attribute::Composure),
 		}
 	}
+	/// Modifiers are read against the current attributes, so changing them needs no
+	/// recalculation.
 	pub fn base_attributes_mut(&mut self) -> &mut Attributes {
 		&mut self._attributes
 	}
@@ -465,24 +576,22 @@ impl Character {
 			_ => 0,
 		};
 
-		let modifier = match &target {
-			ModifierTarget::BaseAttribute(_) => 0,
-			ModifierTarget::BaseSkill(_) => 0,
-			_ => self.modifiers.get_modifier(self, target.clone()),
+		let context = RollContext::default();
+		let base_target = match *target {
+			ModifierTarget::Attribute(attr) => ModifierTarget::BaseAttribute(attr),
+			ModifierTarget::Skill(skill) => ModifierTarget::BaseSkill(skill),
+			_ => target.clone(),
 		};
-		let base_modifier = self.modifiers.get_modifier(
-			self,
-			match *target {
-				ModifierTarget::Attribute(attr) => ModifierTarget::BaseAttribute(attr),
-				ModifierTarget::Skill(skill) => ModifierTarget::BaseSkill(skill),
-				_ => target.clone(),
-			},
-		);
 
-		if add(base, base_modifier) > 5 {
-			add(base, modifier)
-		} else {
-			add(base, base_modifier + modifier)
+		// Bonuses to the base trait don't raise it above 5.
+		let mut value = self.modifiers.apply(self, base_target, base, &context);
+		if value > 5 {
+			value = base;
+		}
+
+		match &target {
+			ModifierTarget::BaseAttribute(_) | ModifierTarget::BaseSkill(_) => value,
+			_ => self.modifiers.apply(self, target.clone(), value, &context),
 		}
 	}
 
@@ -519,24 +628,21 @@ impl Character {
 	pub fn base_skills(&self) -> &Skills {
 		&self.skills
 	}
+	/// Modifiers are read against the current skills, so changing them needs no
+	/// recalculation.
 	pub fn base_skills_mut(&mut self) -> &mut Skills {
 		&mut self.skills
 	}
 
 	pub fn max_health(&self) -> u16 {
-		let attributes = self.attributes();
-
-		add(
-			self.size() + attributes.stamina,
-			self.modifiers
-				.get_modifier(self, Trait::DerivedTrait(DerivedTrait::Health)),
-		)
+		self.derived_trait(DerivedTrait::Health)
 	}
 
 	pub fn health(&self) -> &Damage {
 		&self.health
 	}
 
+	/// No modifiers depend on damage, so changing it needs no recalculation.
 	pub fn health_mut(&mut self) -> &mut Damage {
 		&mut self.health
 	}
@@ -552,83 +658,76 @@ impl Character {
 	}
 
 	pub fn max_willpower(&self) -> u16 {
-		let attributes = self.attributes();
-
-		attributes.resolve + attributes.composure
+		self.derived_trait(DerivedTrait::Willpower)
 	}
 
 	pub fn size(&self) -> u16 {
-		add(
-			self.base_size,
-			self.modifiers
-				.get_modifier(self, Trait::DerivedTrait(DerivedTrait::Size)),
-		)
+		self.derived_trait(DerivedTrait::Size)
 	}
 	pub fn speed(&self) -> u16 {
-		let attributes = self.attributes();
-
-		add(
-			5 + attributes.dexterity + attributes.strength,
-			self.modifiers
-				.get_modifier(self, Trait::DerivedTrait(DerivedTrait::Speed)),
-		)
+		self.derived_trait(DerivedTrait::Speed)
 	}
-
-	#[allow(clippy::cast_sign_loss)]
 	pub fn defense(&self) -> u16 {
-		let res = self
-			.get_pool(Trait::DerivedTrait(DerivedTrait::Defense))
-			.unwrap()
-			.value(self);
+		self.derived_trait(DerivedTrait::Defense)
+	}
 
-		if res > 0 {
-			res as u16
-		} else {
-			0
+	/// A derived trait before the modifiers on it, though after those on the traits it's
+	/// derived from. Defense includes any Attribute or Skill swapped into it.
+	pub fn base_derived_trait(&self, trait_: DerivedTrait) -> u16 {
+		let attributes = self.attributes();
+
+		match trait_ {
+			DerivedTrait::Speed => 5 + attributes.dexterity + attributes.strength,
+			DerivedTrait::Defense => u16::try_from(
+				self.get_pool(Trait::DerivedTrait(DerivedTrait::Defense))
+					.unwrap()
+					.value(self),
+			)
+			.unwrap_or(0),
+			DerivedTrait::Initiative => attributes.dexterity + attributes.composure,
+			DerivedTrait::Perception => attributes.wits + attributes.composure,
+			DerivedTrait::Health => self.size() + attributes.stamina,
+			DerivedTrait::Willpower => attributes.resolve + attributes.composure,
+			DerivedTrait::Size => self.base_size,
 		}
 	}
+
+	fn derived_trait(&self, trait_: DerivedTrait) -> u16 {
+		self.modifiers.apply(
+			self,
+			Trait::DerivedTrait(trait_),
+			self.base_derived_trait(trait_),
+			&RollContext::default(),
+		)
+	}
 	pub fn armor(&self) -> ArmorStruct {
 		ArmorStruct {
 			general: self.base_armor.general,
 			ballistic: self.base_armor.ballistic,
 		}
 	}
+	/// No modifiers depend on armor, so changing it needs no recalculation.
 	pub fn base_armor_mut(&mut self) -> &mut ArmorStruct {
 		&mut self.base_armor
 	}
 	pub fn initiative(&self) -> u16 {
-		let attributes = self.attributes();
-
-		add(
-			attributes.dexterity + attributes.composure,
-			self.modifiers
-				.get_modifier(self, Trait::DerivedTrait(DerivedTrait::Initiative)),
-		)
+		self.derived_trait(DerivedTrait::Initiative)
 	}
 	pub fn perception(&self) -> u16 {
-		let attributes = self.attributes();
-
-		add(
-			attributes.wits + attributes.composure,
-			self.modifiers
-				.get_modifier(self, Trait::DerivedTrait(DerivedTrait::Perception)),
-		)
+		self.derived_trait(DerivedTrait::Perception)
 	}
 	pub fn experience(&self) -> u16 {
-		self.beats / 5
+		self.available_beats() / BEATS_PER_EXPERIENCE
 	}
 	pub fn alternate_experience(&self) -> u16 {
-		self.alternate_beats / 5
+		self.available_alternate_beats() / BEATS_PER_EXPERIENCE
 	}
 
 	pub fn max_fuel(&self) -> u16 {
-		match self.power {
-			0 => self.attributes().stamina,
-			1..=4 => 10 + self.power - 1,
-			5..=8 => 10 + (self.power - 4) * 5,
-			9 => 50,
-			10 => 75,
-			_ => 0,
+		if self.power == 0 {
+			self.attributes().stamina
+		} else {
+			self.limits().max_fuel
 		}
 	}
 }
@@ -646,6 +745,7 @@ impl Default for Character {
 			health: Default::default(),
 
 			modifiers: Default::default(),
+			book_modifiers: Default::default(),
 
 			power: Default::default(),
 			integrity: 7,
@@ -653,16 +753,85 @@ impl Default for Character {
 			willpower: Default::default(),
 			beats: Default::default(),
 			alternate_beats: Default::default(),
+			ledger: Default::default(),
 			base_armor: Default::default(),
 			specialties: Default::default(),
 			touchstones: Default::default(),
 			conditions: Default::default(),
+			tilts: Default::default(),
 			aspirations: Default::default(),
 			weapons: Default::default(),
 		}
 	}
 }
 
+impl PrerequisiteSubject for Character {
+	fn template(&self) -> Template {
+		*self.splat
+	}
+
+	fn trait_rating(&self, trait_: &schema_traits::Trait) -> u16 {
+		match trait_ {
+			schema_traits::Trait::Attribute(attr) => *self.attributes().get(attr),
+			schema_traits::Trait::Skill(skill) => self.skills().get(*skill),
+			schema_traits::Trait::DerivedTrait(dt) => self.get_trait(&Trait::DerivedTrait(*dt)),
+			schema_traits::Trait::SupernaturalTolerance(st) => {
+				if self.splat.supernatural_tolerance() == Some(*st) {
+					self.power
+				} else {
+					0
+				}
+			}
+		}
+	}
+
+	fn merit_rating(&self, name: &str) -> u16 {
+		let name = item_key(name);
+		self.merits
+			.iter()
+			.filter(|(merit, _)| item_key(merit.name()) == name)
+			.map(|(_, rating)| *rating)
+			.max()
+			.unwrap_or(0)
+	}
+
+	fn ability_rating(&self, name: &str) -> u16 {
+		let name = item_key(name);
+		self.abilities
+			.iter()
+			.find(|(ability, _)| {
+				!matches!(ability, Ability::Merit(_)) && item_key(ability.name()) == name
+			})
+			.map_or(0, |(_, rating)| *rating)
+	}
+
+	fn has_specialty(&self, skill: Skill, specialty: &str) -> bool {
+		self.specialties.get(&skill).is_some_and(|specialties| {
+			specialties
+				.iter()
+				.any(|s| s.eq_ignore_ascii_case(specialty))
+		})
+	}
+
+	fn is_in_creation(&self) -> bool {
+		Character::is_in_creation(self)
+	}
+
+	fn integrity(&self) -> u16 {
+		self.integrity
+	}
+
+	fn has_splat(&self, name: &str) -> bool {
+		let name = item_key(name);
+		[
+			self.splat.xsplat().map(|splat| item_key(splat.name())),
+			self.splat.ysplat().map(|splat| item_key(splat.name())),
+			self.splat.zsplat().map(|splat| item_key(splat.name())),
+		]
+		.contains(&Some(name))
+	}
+}
+
 fn is_empty(str: &String) -> bool {
 	str.is_empty()
 }
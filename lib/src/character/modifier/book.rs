@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+
+use cofd_schema::{
+	book::{self as schema_book, Book, MeritItem},
+	library::{Library, key},
+	modifiers::{self as schema, ItemModifier},
+	prerequisites::Prerequisites,
+	traits,
+};
+use cofd_util::{AllVariants, VariantName};
+
+use super::{Condition, Modifier, ModifierOp, ModifierTarget, ModifierValue, RollTag};
+use crate::{
+	prelude::Trait,
+	splat::{
+		Merit,
+		werewolf::{Form, MoonGift},
+	},
+};
+
+/// Moon Gifts are named after their moon, e.g. `crescent` for "Crescent Moon's Gift".
+fn moon_gift_key(name: &str) -> String {
+	let name = if name.contains(" of ") {
+		name.rsplit(' ').next()
+	} else {
+		name.split(' ').next()
+	}
+	.unwrap_or(name);
+
+	key(name.trim_end_matches("'s"))
+}
+
+fn rating(rating: u16) -> u8 {
+	u8::try_from(rating).unwrap_or(u8::MAX)
+}
+
+/// Modifiers defined by the items of loaded [`Book`]s, looked up by item name.
+///
+/// When several books define the same item, the first one loaded wins, as in a
+/// [`Library`].
+#[derive(Debug, Default, Clone)]
+pub struct BookModifiers {
+	/// Modifiers of each merit, along with the prerequisites of the sub-item they come
+	/// from, if any.
+	merits: HashMap<String, Vec<(ItemModifier, Prerequisites)>>,
+	/// Modifiers of each Moon Gift's facets, along with the Renown they need.
+	moon_gifts: HashMap<String, Vec<(u8, ItemModifier)>>,
+}
+
+impl BookModifiers {
+	pub fn new<'a>(books: impl IntoIterator<Item = &'a Book>) -> Self {
+		let mut modifiers = Self::default();
+		for book in books {
+			modifiers.add_book(book);
+		}
+		modifiers
+	}
+
+	pub fn add_book(&mut self, book: &Book) {
+		for merit in &book.merits {
+			self.add_merit(merit);
+		}
+		for gift in &book.moon_gifts {
+			self.add_moon_gift(gift);
+		}
+	}
+
+	/// Sub-items' modifiers, e.g. those of a Fighting Style's maneuvers, become the
+	/// merit's from the rating that unlocks them, while the character meets the sub-item's
+	/// prerequisites.
+	fn add_merit(&mut self, merit: &MeritItem) {
+		let modifiers: Vec<_> = merit
+			.modifiers
+			.iter()
+			.map(|modifier| (modifier.clone(), Prerequisites::default()))
+			.chain(merit.inner.children.iter().flat_map(|child| {
+				child.modifiers.iter().map(|modifier| {
+					let min_rating = modifier.min_rating.max(child.min_rating());
+					(
+						modifier.clone().with_min_rating(min_rating),
+						child.prerequisites.clone(),
+					)
+				})
+			}))
+			.collect();
+
+		if !modifiers.is_empty() {
+			self.merits.entry(key(&merit.name)).or_insert(modifiers);
+		}
+	}
+
+	fn add_moon_gift(&mut self, gift: &schema_book::MoonGift) {
+		let facets: Vec<_> = gift
+			.facets
+			.iter()
+			.flat_map(|facet| {
+				facet
+					.modifiers
+					.iter()
+					.map(|modifier| (facet.inner.inner.level, modifier.clone()))
+			})
+			.collect();
+
+		if !facets.is_empty() {
+			self.moon_gifts
+				.entry(moon_gift_key(&gift.name))
+				.or_insert(facets);
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.merits.is_empty() && self.moon_gifts.is_empty()
+	}
+
+	/// Whether a loaded item defines modifiers for the merit, which then replace its
+	/// built-in ones.
+	pub fn has_merit(&self, merit: &Merit) -> bool {
+		self.merits.contains_key(&key(merit.name()))
+	}
+
+	/// Whether a loaded item defines modifiers for the Moon Gift's facets, which then
+	/// replace its built-in ones.
+	pub fn has_moon_gift(&self, moon_gift: &MoonGift) -> bool {
+		self.moon_gifts.contains_key(&key(moon_gift.name()))
+	}
+
+	/// Modifiers the merit provides at the given rating.
+	pub fn merit(&self, merit: &Merit, rating: u16) -> Vec<Modifier> {
+		let rating = self::rating(rating);
+
+		self.merits
+			.get(&key(merit.name()))
+			.into_iter()
+			.flatten()
+			.filter(|(modifier, _)| modifier.applies_at(rating))
+			.filter_map(|(modifier, prerequisites)| {
+				let mut modifier = to_modifier(modifier, rating)?;
+				if !prerequisites.is_empty() {
+					let prerequisites = Condition::Prerequisites(prerequisites.clone());
+					modifier.condition = Some(match modifier.condition {
+						Some(condition) => condition & prerequisites,
+						None => prerequisites,
+					});
+				}
+				Some(modifier)
+			})
+			.collect()
+	}
+
+	/// Modifiers of the Moon Gift's facets unlocked at the given Renown.
+	pub fn moon_gift(&self, moon_gift: &MoonGift, renown: u16) -> Vec<Modifier> {
+		let renown = rating(renown);
+
+		self.moon_gifts
+			.get(&key(moon_gift.name()))
+			.into_iter()
+			.flatten()
+			.filter(|(level, modifier)| *level <= renown && modifier.applies_at(renown))
+			.filter_map(|(_, modifier)| to_modifier(modifier, renown))
+			.collect()
+	}
+}
+
+impl From<&Library> for BookModifiers {
+	/// Modifiers of the library's items, using the first definition of each.
+	fn from(library: &Library) -> Self {
+		let mut modifiers = Self::default();
+		for merit in library.merits() {
+			modifiers.add_merit(merit);
+		}
+		for gift in library.moon_gifts() {
+			modifiers.add_moon_gift(gift);
+		}
+		modifiers
+	}
+}
+
+/// Converts a book modifier at the item's rating, `None` if it can't apply to a
+/// character, e.g. because it names an unknown form.
+fn to_modifier(modifier: &ItemModifier, rating: u8) -> Option<Modifier> {
+	let value = match &modifier.value {
+		schema::ModifierValue::Skill(skill) => ModifierValue::Skill(*skill),
+		schema::ModifierValue::DicePool(pool) => ModifierValue::DicePool(pool.clone()),
+		value => ModifierValue::Num(value.at_rating(rating)?),
+	};
+
+	let mut result = Modifier::new(modifier.target, value, modifier.op.into());
+	if let Some(condition) = &modifier.condition {
+		result.condition = Some(to_condition(condition)?);
+	}
+
+	Some(result)
+}
+
+fn to_condition(condition: &schema::ModifierCondition) -> Option<Condition> {
+	Some(match condition {
+		schema::ModifierCondition::Form(name) => Condition::WerewolfForm(
+			Form::all()
+				.into_iter()
+				.find(|form| form.name().eq_ignore_ascii_case(name))?,
+		),
+		schema::ModifierCondition::Skill(skill) => Condition::Skill(*skill),
+		schema::ModifierCondition::Specialty => Condition::Specialty,
+		schema::ModifierCondition::Tag(tag) => Condition::Tag(RollTag::from(tag.as_str())),
+		schema::ModifierCondition::All(conditions) => {
+			Condition::All(conditions.iter().map(to_condition).collect::<Option<_>>()?)
+		}
+		schema::ModifierCondition::Any(conditions) => {
+			Condition::Any(conditions.iter().map(to_condition).collect::<Option<_>>()?)
+		}
+		schema::ModifierCondition::Not(condition) => {
+			Condition::Not(Box::new(to_condition(condition)?))
+		}
+	})
+}
+
+impl From<schema::ModifierOp> for ModifierOp {
+	fn from(op: schema::ModifierOp) -> Self {
+		match op {
+			schema::ModifierOp::Add => ModifierOp::Add,
+			schema::ModifierOp::Set => ModifierOp::Set,
+			schema::ModifierOp::Min => ModifierOp::Min,
+			schema::ModifierOp::Max => ModifierOp::Max,
+			schema::ModifierOp::Div => ModifierOp::Div,
+		}
+	}
+}
+
+impl From<schema::ModifierTarget> for ModifierTarget {
+	fn from(target: schema::ModifierTarget) -> Self {
+		match target {
+			schema::ModifierTarget::BaseAttribute(attr) => ModifierTarget::BaseAttribute(attr),
+			schema::ModifierTarget::BaseSkill(skill) => ModifierTarget::BaseSkill(skill),
+			schema::ModifierTarget::Trait(traits::Trait::Attribute(attr)) => {
+				ModifierTarget::Attribute(attr)
+			}
+			schema::ModifierTarget::Trait(traits::Trait::Skill(skill)) => {
+				ModifierTarget::Skill(skill)
+			}
+			schema::ModifierTarget::Trait(traits::Trait::DerivedTrait(trait_)) => {
+				ModifierTarget::Trait(Trait::DerivedTrait(trait_))
+			}
+			schema::ModifierTarget::Trait(traits::Trait::SupernaturalTolerance(_)) => {
+				ModifierTarget::Trait(Trait::Power)
+			}
+			schema::ModifierTarget::Roll => ModifierTarget::Roll,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::*;
+	use crate::{
+		character::{Character, modifier::RollContext},
+		prelude::Skill,
+		splat::werewolf::{Auspice, Renown, Werewolf},
+	};
+
+	const BOOK: &str = r#"(
+		info: (name: "Homebrew", id: Codex, hash: "0", publicationDate: "2024-01-01"),
+		merits: [
+			{
+				"name": "Fast Reflexes",
+				"reference": (CofD, 62),
+				"modifiers": [(target: Trait(Initiative), value: PerDot(1))],
+				"dotRating": (start: 1, end: 3),
+			},
+			{
+				"name": "Iron Hide",
+				"reference": (Codex, 1),
+				"modifiers": [
+					(target: Trait(Health), value: ByRating([1, 1, 2]), minRating: 2),
+					(target: Trait(Defense), value: Skill(Brawl), op: Set, condition: Some(Not(Tag("firearm")))),
+				],
+				"dotRating": (start: 1, end: 3),
+			},
+			{
+				"name": "Giant",
+				"reference": (CofD, 44),
+				"modifiers": [(target: Trait(Size), value: Num(1))],
+				"dotRating": 3,
+			},
+		],
+		moon_gifts: [
+			(
+				name: "Full Moon's Gift",
+				kind: Moon,
+				facets: [
+					{
+						"name": "Killer Instinct",
+						"reference": (WtF2e, 117),
+						"modifiers": [(target: Trait(Brawl), value: Num(1), condition: Some(Form("Gauru")))],
+						"auspice": Rahu,
+						"level": 1,
+					},
+				],
+			),
+		],
+	)"#;
+
+	#[test]
+	fn book_modifiers() {
+		let book: Book = ron::from_str(BOOK).unwrap();
+		let modifiers = Arc::new(BookModifiers::new([&book]));
+
+		assert_eq!(modifiers.merit(&Merit::FastReflexes, 2)[0].val(), Some(2));
+		assert!(modifiers.merit(&Merit::FastReflexes, 0).is_empty());
+
+		let character = Character::builder()
+			.with_merits([(Merit::Custom("Iron Hide".to_owned()), 3)])
+			.with_book_modifiers(modifiers.clone())
+			.build();
+		assert_eq!(character.max_health(), 8);
+		assert_eq!(character.defense(), 1);
+
+		// The book's Giant replaces the built-in one rather than adding to it.
+		let giant = Character::builder()
+			.with_merits([(Merit::Giant, 3)])
+			.with_book_modifiers(modifiers.clone())
+			.build();
+		assert_eq!(giant.size(), 6);
+
+		let werewolf = Character::builder()
+			.with_splat(Werewolf::default().with_auspice(Auspice::Rahu))
+			.with_abilities([(Renown::Purity.into(), 1)])
+			.with_book_modifiers(modifiers)
+			.build();
+		assert_eq!(werewolf.get_modifier(Skill::Brawl), 0);
+		assert_eq!(
+			werewolf.get_conditional_modifier(
+				Skill::Brawl,
+				&RollContext::default().with_form(Form::Gauru)
+			),
+			Some(1)
+		);
+	}
+
+	#[test]
+	fn homebrew() {
+		const HOMEBREW: &str = r#"(
+			info: (name: "Homebrew", id: Codex),
+			merits: [
+				{
+					"name": "Fast Reflexes",
+					"modifiers": [(target: Trait(Initiative), value: Num(5))],
+					"dotRating": (start: 1, end: 3),
+				},
+				{
+					"name": "Hobbled",
+					"modifiers": [
+						(target: Trait(Speed), value: Num(2), op: Div),
+						(target: Roll, value: Num(-1)),
+					],
+					"dotRating": 1,
+				},
+			],
+		)"#;
+
+		let book: Book = ron::from_str(BOOK).unwrap();
+		let homebrew: Book = ron::from_str(HOMEBREW).unwrap();
+		let modifiers = Arc::new(BookModifiers::new([&book, &homebrew]));
+
+		// The first book loaded wins.
+		assert_eq!(modifiers.merit(&Merit::FastReflexes, 2)[0].val(), Some(2));
+
+		let character = Character::builder()
+			.with_merits([(Merit::Custom("Hobbled".to_owned()), 1)])
+			.with_book_modifiers(modifiers)
+			.build();
+		assert_eq!(character.speed(), 3);
+		assert_eq!(character.get_modifier(ModifierTarget::Roll), -1);
+	}
+}
@@ -0,0 +1,210 @@
+use std::ops::{BitAnd, BitOr, Not};
+
+use cofd_schema::prerequisites::Prerequisites;
+use cofd_util::{AllVariants, VariantName};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	prelude::{Character, Skill},
+	splat::{Splat, werewolf::Form},
+};
+
+/// Circumstances of a roll that a [`Condition`] can test for.
+#[derive(Clone, Hash, Debug, PartialEq, Eq, Serialize, Deserialize, VariantName, AllVariants)]
+pub enum RollTag {
+	Firearm,
+	Melee,
+	Unarmed,
+	Thrown,
+	Darkness,
+	/// The roll targets or opposes a supernatural creature.
+	SupernaturalOpponent,
+	/// A changeling's fae mien is showing.
+	FaeMien,
+	/// The roll relies on an arm hurt by Arm Wrack.
+	InjuredArm,
+	/// The roll resists Empathy, Intimidation, Subterfuge or another social approach.
+	SocialDefense,
+	/// The roll is made in the face of what frightens the character.
+	Fear,
+	Custom(String),
+}
+
+impl From<&str> for RollTag {
+	/// Looks a tag up by its kebab-case name, e.g. `supernatural-opponent`.
+	fn from(name: &str) -> Self {
+		RollTag::all()
+			.into_iter()
+			.find(|tag| !name.is_empty() && tag.name() == name)
+			.unwrap_or_else(|| RollTag::Custom(name.to_owned()))
+	}
+}
+
+/// What a roll is being made with and under which circumstances.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RollContext {
+	pub skill: Option<Skill>,
+	/// The Specialty claimed for the roll.
+	pub specialty: Option<String>,
+	/// A Werewolf form to evaluate in, instead of the current one.
+	pub form: Option<Form>,
+	pub tags: Vec<RollTag>,
+}
+
+impl RollContext {
+	#[must_use]
+	pub fn with_skill(mut self, skill: Skill) -> Self {
+		self.skill = Some(skill);
+		self
+	}
+
+	#[must_use]
+	pub fn with_specialty(mut self, specialty: impl Into<String>) -> Self {
+		self.specialty = Some(specialty.into());
+		self
+	}
+
+	#[must_use]
+	pub fn with_form(mut self, form: Form) -> Self {
+		self.form = Some(form);
+		self
+	}
+
+	#[must_use]
+	pub fn with_tag(mut self, tag: RollTag) -> Self {
+		self.tags.push(tag);
+		self
+	}
+}
+
+/// When a conditional [`Modifier`](super::Modifier) applies.
+///
+/// Conditions combine with `&`, `|` and `!`.
+#[derive(Clone, Hash, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Condition {
+	WerewolfForm(Form),
+	/// The roll uses this Skill.
+	Skill(Skill),
+	/// The character has the Specialty claimed for the roll's Skill.
+	Specialty,
+	Tag(RollTag),
+	/// The character meets the prerequisites, e.g. those of a Fighting Style's maneuver.
+	///
+	/// They're checked against modified traits, so a modifier with this condition mustn't
+	/// change a trait the prerequisites depend on.
+	Prerequisites(Prerequisites),
+	All(Vec<Condition>),
+	Any(Vec<Condition>),
+	Not(Box<Condition>),
+}
+
+impl Condition {
+	pub fn check(&self, character: &Character, context: &RollContext) -> bool {
+		match self {
+			Self::WerewolfForm(form) => match (&context.form, &character.splat) {
+				(Some(context_form), Splat::Werewolf(_)) => form.eq(context_form),
+				(None, Splat::Werewolf(data)) => form.eq(&data.form),
+				_ => false,
+			},
+			Self::Skill(skill) => context.skill == Some(*skill),
+			Self::Specialty => {
+				if let (Some(skill), Some(specialty)) = (&context.skill, &context.specialty) {
+					character
+						.specialties
+						.get(skill)
+						.is_some_and(|specialties| specialties.contains(specialty))
+				} else {
+					false
+				}
+			}
+			Self::Tag(tag) => context.tags.contains(tag),
+			Self::Prerequisites(prerequisites) => prerequisites.check(character).is_met(),
+			Self::All(conditions) => conditions.iter().all(|c| c.check(character, context)),
+			Self::Any(conditions) => conditions.iter().any(|c| c.check(character, context)),
+			Self::Not(condition) => !condition.check(character, context),
+		}
+	}
+}
+
+impl From<Form> for Condition {
+	fn from(value: Form) -> Self {
+		Condition::WerewolfForm(value)
+	}
+}
+
+impl From<Skill> for Condition {
+	fn from(value: Skill) -> Self {
+		Condition::Skill(value)
+	}
+}
+
+impl From<RollTag> for Condition {
+	fn from(value: RollTag) -> Self {
+		Condition::Tag(value)
+	}
+}
+
+impl BitAnd for Condition {
+	type Output = Self;
+
+	fn bitand(self, rhs: Self) -> Self::Output {
+		match self {
+			Condition::All(mut conditions) => {
+				conditions.push(rhs);
+				Condition::All(conditions)
+			}
+			_ => Condition::All(vec![self, rhs]),
+		}
+	}
+}
+
+impl BitOr for Condition {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self::Output {
+		match self {
+			Condition::Any(mut conditions) => {
+				conditions.push(rhs);
+				Condition::Any(conditions)
+			}
+			_ => Condition::Any(vec![self, rhs]),
+		}
+	}
+}
+
+impl Not for Condition {
+	type Output = Self;
+
+	fn not(self) -> Self::Output {
+		match self {
+			Condition::Not(condition) => *condition,
+			_ => Condition::Not(Box::new(self)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::splat::werewolf::Werewolf;
+
+	#[test]
+	fn check() {
+		let character = Character::builder()
+			.with_splat(Werewolf::default())
+			.with_specialties(Skill::Firearms, vec!["Rifles".into()])
+			.build();
+
+		let condition = (Condition::from(Skill::Firearms) & Condition::Specialty)
+			| (Condition::from(Form::Gauru) & !Condition::from(RollTag::Darkness));
+
+		let context = RollContext::default().with_skill(Skill::Firearms);
+		assert!(!condition.check(&character, &context));
+		assert!(condition.check(&character, &context.clone().with_specialty("Rifles")));
+		assert!(condition.check(&character, &context.clone().with_form(Form::Gauru)));
+		assert!(!condition.check(
+			&character,
+			&context.with_form(Form::Gauru).with_tag(RollTag::Darkness)
+		));
+	}
+}
@@ -0,0 +1,847 @@
+use std::{
+	collections::{HashMap, HashSet},
+	sync::OnceLock,
+};
+
+use cofd_schema::traits::DerivedTrait;
+use cofd_util::VariantName;
+use serde::{Deserialize, Serialize};
+
+mod book;
+mod condition;
+pub use book::BookModifiers;
+pub use condition::{Condition, RollContext, RollTag};
+
+use super::{
+	Character, add,
+	conditions::{self, Tilt},
+};
+use crate::{
+	dice_pool::{DicePool, DicePoolExt},
+	prelude::Trait,
+	splat::{
+		Merit, Splat,
+		ability::Ability,
+		werewolf::{Form, MoonGift},
+	},
+	traits::{attribute::Attribute, skill::Skill},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Modifier {
+	pub target: ModifierTarget,
+	pub value: ModifierValue,
+	pub op: ModifierOp,
+	pub condition: Option<Condition>,
+	pub stacking: Stacking,
+	/// Among `Set` modifiers on the same target, the highest priority wins.
+	pub priority: i8,
+}
+
+impl Modifier {
+	pub fn new(
+		target: impl Into<ModifierTarget>,
+		value: impl Into<ModifierValue>,
+		op: ModifierOp,
+	) -> Self {
+		Self {
+			target: target.into(),
+			value: value.into(),
+			op,
+			condition: None,
+			stacking: Stacking::default(),
+			priority: 0,
+		}
+	}
+
+	pub fn conditional(
+		target: impl Into<ModifierTarget>,
+		value: impl Into<ModifierValue>,
+		op: ModifierOp,
+		condition: impl Into<Condition>,
+	) -> Self {
+		Self {
+			target: target.into(),
+			value: value.into(),
+			op,
+			condition: Some(condition.into()),
+			stacking: Stacking::default(),
+			priority: 0,
+		}
+	}
+
+	#[must_use]
+	pub fn with_stacking(mut self, stacking: Stacking) -> Self {
+		self.stacking = stacking;
+		self
+	}
+
+	#[must_use]
+	pub fn with_priority(mut self, priority: i8) -> Self {
+		self.priority = priority;
+		self
+	}
+
+	/// A `Set` to a Skill or dice pool, which swaps that part of a pool such as Defense's
+	/// rather than replacing the trait's value.
+	pub fn is_swap(&self) -> bool {
+		self.op == ModifierOp::Set
+			&& matches!(
+				self.value,
+				ModifierValue::Skill(_) | ModifierValue::DicePool(_)
+			)
+	}
+
+	pub fn val(&self) -> Option<i16> {
+		match self.value {
+			ModifierValue::Num(val) => Some(val),
+			ModifierValue::Skill(_) | ModifierValue::Ability(_) => None,
+			ModifierValue::DicePool(_) => unreachable!(),
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Serialize, Deserialize)]
+pub enum ModifierTarget {
+	BaseAttribute(Attribute),
+	BaseSkill(Skill),
+	Attribute(Attribute),
+	Skill(Skill),
+	Trait(Trait),
+	/// The dice pool of every roll, e.g. a Tilt's penalty. Applied when the roll is
+	/// prepared.
+	Roll,
+}
+
+impl From<Attribute> for ModifierTarget {
+	fn from(attr: Attribute) -> Self {
+		ModifierTarget::Attribute(attr)
+	}
+}
+
+impl From<Skill> for ModifierTarget {
+	fn from(value: Skill) -> Self {
+		ModifierTarget::Skill(value)
+	}
+}
+
+impl From<Trait> for ModifierTarget {
+	fn from(value: Trait) -> Self {
+		ModifierTarget::Trait(value)
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModifierValue {
+	Num(i16),
+	Ability(Ability),
+	Skill(Skill),
+	DicePool(DicePool),
+}
+
+impl ModifierValue {
+	pub fn value(&self, character: &Character) -> i16 {
+		match self {
+			ModifierValue::Num(value) => *value,
+			ModifierValue::Ability(ability) => {
+				*character.get_ability_value(ability).unwrap_or(&0) as i16
+			}
+			ModifierValue::Skill(skill) => character.skills.get(*skill) as i16,
+			ModifierValue::DicePool(pool) => i16::from(pool.value(character)),
+		}
+	}
+}
+
+impl From<i16> for ModifierValue {
+	fn from(value: i16) -> Self {
+		ModifierValue::Num(value)
+	}
+}
+
+impl From<u16> for ModifierValue {
+	fn from(value: u16) -> Self {
+		ModifierValue::Num(value as i16)
+	}
+}
+
+impl From<i32> for ModifierValue {
+	fn from(value: i32) -> Self {
+		ModifierValue::Num(value as i16)
+	}
+}
+
+impl From<Ability> for ModifierValue {
+	fn from(value: Ability) -> Self {
+		ModifierValue::Ability(value)
+	}
+}
+
+impl From<Skill> for ModifierValue {
+	fn from(value: Skill) -> Self {
+		ModifierValue::Skill(value)
+	}
+}
+
+impl From<DicePool> for ModifierValue {
+	fn from(value: DicePool) -> Self {
+		ModifierValue::DicePool(value)
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, VariantName)]
+pub enum ModifierOp {
+	Add,
+	/// Replaces the trait's value, overriding any `Add` modifiers.
+	Set,
+	/// The trait can't go below the value.
+	Min,
+	/// The trait can't go above the value.
+	Max,
+	/// Divides the trait by the value after any `Add` modifiers, rounding down.
+	Div,
+}
+
+/// How an `Add` modifier combines with others on the same target.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stacking {
+	#[default]
+	Stack,
+	/// Only the largest bonus and the largest penalty from the same source apply.
+	Source,
+}
+
+/// Where a [`Modifier`] comes from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ModifierSource {
+	/// A discipline, moon gift or other ability.
+	Ability(Ability),
+	/// The merit at `index` in the character's merits.
+	Merit {
+		index: usize,
+		merit: Merit,
+	},
+	/// Weapons, armor and other gear.
+	Equipment(String),
+	/// A Werewolf form.
+	Form(Form),
+	/// Bonuses granted by the character's template, e.g. a clan's favored attribute.
+	Template,
+	Condition(conditions::Condition),
+	Tilt(Tilt),
+}
+
+impl std::fmt::Display for ModifierSource {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ModifierSource::Ability(ability) => f.write_str(ability.name()),
+			ModifierSource::Merit { merit, .. } => f.write_str(merit.name()),
+			ModifierSource::Equipment(name) => f.write_str(name),
+			ModifierSource::Form(form) => write!(f, "{} form", form.name()),
+			ModifierSource::Template => f.write_str("template"),
+			ModifierSource::Condition(condition) => f.write_str(condition.name()),
+			ModifierSource::Tilt(tilt) => f.write_str(tilt.name()),
+		}
+	}
+}
+
+/// A modifier on a target, as listed by [`Modifiers::active`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveModifier {
+	pub source: ModifierSource,
+	pub op: ModifierOp,
+	pub value: i16,
+	pub condition: Option<Condition>,
+	/// Whether the modifier counts towards the value, rather than being overridden by
+	/// a `Set`, not stacking with another from the same source, or being a swap that
+	/// changes the trait's pool instead.
+	pub applied: bool,
+}
+
+/// What a group of modifiers is calculated from, so that a change to the character
+/// only recalculates the modifiers that depend on it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dependency {
+	/// The ability's rating.
+	Ability(Ability),
+	/// The character's merits and their ratings. Merits are tracked by index, so every
+	/// merit is recalculated together.
+	Merits,
+	/// The character's splat, e.g. its clan, order or auspice.
+	///
+	/// Werewolf forms aren't a dependency, as form modifiers are conditional and
+	/// checked against the current form when they're read.
+	Template,
+	/// The character's Conditions and Tilts.
+	Conditions,
+}
+
+type Entry = (Dependency, ModifierSource, Modifier);
+
+#[derive(Debug, Default, Clone)]
+struct ModifierMap {
+	targets: HashMap<ModifierTarget, Vec<Entry>>,
+	/// Targets each dependency has modifiers on.
+	dependencies: HashMap<Dependency, HashSet<ModifierTarget>>,
+}
+
+impl ModifierMap {
+	fn new(character: &Character) -> Self {
+		let mut map = Self::default();
+		for dependency in Modifiers::dependencies(character) {
+			let modifiers = Modifiers::collect_dependency(character, &dependency);
+			map.insert(dependency, modifiers);
+		}
+		map
+	}
+
+	fn insert(&mut self, dependency: Dependency, modifiers: Vec<(ModifierSource, Modifier)>) {
+		if modifiers.is_empty() {
+			return;
+		}
+
+		let mut targets = HashSet::new();
+		for (source, modifier) in modifiers {
+			targets.insert(modifier.target.clone());
+			self.targets
+				.entry(modifier.target.clone())
+				.or_default()
+				.push((dependency.clone(), source, modifier));
+		}
+		self.dependencies
+			.entry(dependency)
+			.or_default()
+			.extend(targets);
+	}
+
+	/// Removes the dependency's modifiers, only touching the targets it had modifiers on.
+	fn remove(&mut self, dependency: &Dependency) {
+		for target in self.dependencies.remove(dependency).into_iter().flatten() {
+			if let Some(entries) = self.targets.get_mut(&target) {
+				entries.retain(|(other, ..)| other != dependency);
+				if entries.is_empty() {
+					self.targets.remove(&target);
+				}
+			}
+		}
+	}
+}
+
+/// The character's modifiers, indexed by target.
+///
+/// The map is built on first read, e.g. after deserializing, and after that only
+/// changed dependencies are recalculated. Reads don't lock.
+#[derive(Debug, Default, Clone)]
+pub struct Modifiers {
+	map: OnceLock<ModifierMap>,
+}
+
+impl Modifiers {
+	pub fn new(character: &Character) -> Self {
+		Self {
+			map: OnceLock::from(ModifierMap::new(character)),
+		}
+	}
+
+	/// Every dependency the character currently has modifiers from.
+	pub fn dependencies(character: &Character) -> Vec<Dependency> {
+		character
+			.abilities
+			.keys()
+			.cloned()
+			.map(Dependency::Ability)
+			.chain([
+				Dependency::Merits,
+				Dependency::Template,
+				Dependency::Conditions,
+			])
+			.collect()
+	}
+
+	/// Every modifier the character's abilities, merits and splat provide, along with
+	/// where it came from.
+	pub fn collect(character: &Character) -> Vec<(ModifierSource, Modifier)> {
+		Self::dependencies(character)
+			.iter()
+			.flat_map(|dependency| Self::collect_dependency(character, dependency))
+			.collect()
+	}
+
+	/// The modifiers calculated from a single dependency.
+	pub fn collect_dependency(
+		character: &Character,
+		dependency: &Dependency,
+	) -> Vec<(ModifierSource, Modifier)> {
+		match dependency {
+			Dependency::Ability(ability) => character
+				.get_ability_value(ability)
+				.map(|val| match ability {
+					Ability::Merit(merit) => Self::merit_modifiers(character, merit, *val),
+					Ability::MoonGift(moon_gift) => {
+						Self::moon_gift_modifiers(character, moon_gift, *val)
+					}
+					_ => ability.get_modifiers(*val),
+				})
+				.unwrap_or_default()
+				.into_iter()
+				.map(|modifier| (ModifierSource::Ability(ability.clone()), modifier))
+				.collect(),
+			Dependency::Merits => character
+				.merits
+				.iter()
+				.enumerate()
+				.flat_map(|(index, (merit, val))| {
+					Self::merit_modifiers(character, merit, *val)
+						.into_iter()
+						.map(move |modifier| {
+							(
+								ModifierSource::Merit {
+									index,
+									merit: merit.clone(),
+								},
+								modifier,
+							)
+						})
+				})
+				.collect(),
+			Dependency::Template => Self::collect_template(character),
+			Dependency::Conditions => character
+				.conditions
+				.iter()
+				.flat_map(|condition| {
+					condition
+						.get_modifiers()
+						.into_iter()
+						.map(|modifier| (ModifierSource::Condition(condition.clone()), modifier))
+				})
+				.chain(character.tilts.iter().flat_map(|tilt| {
+					tilt.get_modifiers()
+						.into_iter()
+						.map(|modifier| (ModifierSource::Tilt(tilt.clone()), modifier))
+				}))
+				.collect(),
+		}
+	}
+
+	/// The merit's modifiers, from its book item if that defines any, otherwise built in.
+	fn merit_modifiers(character: &Character, merit: &Merit, rating: u16) -> Vec<Modifier> {
+		if character.book_modifiers.has_merit(merit) {
+			character.book_modifiers.merit(merit, rating)
+		} else {
+			merit.get_modifiers(rating)
+		}
+	}
+
+	/// The Moon Gift's modifiers, from its book item if that defines any, otherwise built in.
+	fn moon_gift_modifiers(
+		character: &Character,
+		moon_gift: &MoonGift,
+		renown: u16,
+	) -> Vec<Modifier> {
+		if character.book_modifiers.has_moon_gift(moon_gift) {
+			character.book_modifiers.moon_gift(moon_gift, renown)
+		} else {
+			moon_gift.get_modifiers(renown)
+		}
+	}
+
+	fn collect_template(character: &Character) -> Vec<(ModifierSource, Modifier)> {
+		let mut modifiers: Vec<(ModifierSource, Modifier)> = Vec::new();
+
+		match &character.splat {
+			Splat::Werewolf(data) => {
+				// modifiers.extend(data.form.get_modifiers());
+				modifiers.extend(Form::modifiers().into_iter().map(|modifier| {
+					let source = match &modifier.condition {
+						Some(Condition::WerewolfForm(form)) => ModifierSource::Form(form.clone()),
+						_ => ModifierSource::Template,
+					};
+					(source, modifier)
+				}));
+
+				if let Some(auspice) = &data.auspice {
+					let moon_gift = auspice.get_moon_gift();
+					let renown = *character
+						.get_ability_value(&auspice.get_renown().clone().into())
+						.unwrap_or(&0);
+					modifiers.extend(
+						Self::moon_gift_modifiers(character, moon_gift, renown)
+							.into_iter()
+							.map(|modifier| {
+								(ModifierSource::Ability(moon_gift.clone().into()), modifier)
+							}),
+					);
+
+					if let Some(skill_bonus) = data.skill_bonus() {
+						if auspice.skills().contains(skill_bonus) {
+							modifiers.push((
+								ModifierSource::Template,
+								Modifier::new(
+									ModifierTarget::BaseSkill(*skill_bonus),
+									1,
+									ModifierOp::Add,
+								),
+							));
+						}
+					}
+				}
+			}
+			Splat::Mage(data) => {
+				// TODO: High Speech merit, Order Status merit
+				if data.order.is_some() {
+					modifiers.push((
+						ModifierSource::Template,
+						Modifier::new(ModifierTarget::BaseSkill(Skill::Occult), 1, ModifierOp::Add),
+					));
+				}
+
+				let attr_bonus = data.attr_bonus();
+				modifiers.push((
+					ModifierSource::Template,
+					Modifier::new(
+						ModifierTarget::BaseAttribute(*attr_bonus),
+						1,
+						ModifierOp::Add,
+					),
+				));
+			}
+			Splat::Vampire(data) => {
+				modifiers.push((
+					ModifierSource::Template,
+					Modifier::new(
+						ModifierTarget::BaseAttribute(*data.attr_bonus()),
+						1,
+						ModifierOp::Add,
+					),
+				));
+			}
+			Splat::Changeling(data) => {
+				modifiers.push((
+					ModifierSource::Template,
+					Modifier::new(
+						ModifierTarget::BaseAttribute(*data.attr_bonus()),
+						1,
+						ModifierOp::Add,
+					),
+				));
+			}
+
+			_ => {}
+		}
+
+		modifiers
+	}
+
+	/// Recalculates every modifier.
+	///
+	/// Prefer [`Modifiers::replace`] when it's known what changed.
+	pub fn update(&mut self, character: &Character) {
+		self.map = OnceLock::from(ModifierMap::new(character));
+	}
+
+	/// Drops the calculated modifiers, so they're calculated from scratch on the next read.
+	pub fn clear(&mut self) {
+		self.map = OnceLock::new();
+	}
+
+	/// Whether the modifiers have been calculated yet.
+	pub fn is_calculated(&self) -> bool {
+		self.map.get().is_some()
+	}
+
+	/// Replaces the modifiers calculated from `dependency`.
+	///
+	/// Does nothing if the modifiers haven't been calculated yet, as they'll be
+	/// calculated from scratch on the next read.
+	pub fn replace(&mut self, dependency: Dependency, modifiers: Vec<(ModifierSource, Modifier)>) {
+		if let Some(map) = self.map.get_mut() {
+			map.remove(&dependency);
+			map.insert(dependency, modifiers);
+		}
+	}
+
+	fn entries(&self, character: &Character, target: &ModifierTarget) -> &[Entry] {
+		self.map
+			.get_or_init(|| ModifierMap::new(character))
+			.targets
+			.get(target)
+			.map_or(&[], Vec::as_slice)
+	}
+
+	/// Every modifier on `target` whose condition holds in `context`, with its value and
+	/// whether it applies under the stacking rules.
+	///
+	/// Reads straight from the map without allocating, so that reading a trait stays cheap.
+	fn resolve<'a>(
+		&'a self,
+		character: &'a Character,
+		target: &ModifierTarget,
+		context: &'a RollContext,
+	) -> impl Iterator<Item = (&'a ModifierSource, &'a Modifier, i16, bool)> + Clone + 'a {
+		let modifiers = self
+			.entries(character, target)
+			.iter()
+			.filter(move |(_, _, modifier)| {
+				modifier
+					.condition
+					.as_ref()
+					.is_none_or(|condition| condition.check(character, context))
+			})
+			.map(move |(_, source, modifier)| (source, modifier, modifier.value.value(character)));
+
+		let set = modifiers
+			.clone()
+			.enumerate()
+			.filter(|(_, (_, modifier, _))| modifier.op == ModifierOp::Set && !modifier.is_swap())
+			.max_by_key(|(_, (_, modifier, value))| (modifier.priority, *value))
+			.map(|(i, _)| i);
+
+		let others = modifiers.clone();
+		modifiers
+			.enumerate()
+			.map(move |(i, (source, modifier, value))| {
+				let applied = match modifier.op {
+					ModifierOp::Set => set == Some(i),
+					ModifierOp::Min | ModifierOp::Max | ModifierOp::Div => true,
+					ModifierOp::Add if set.is_some() => false,
+					ModifierOp::Add => match modifier.stacking {
+						Stacking::Stack => true,
+						// The first of the largest same-signed values from the source wins.
+						Stacking::Source => !others.clone().enumerate().any(
+							|(j, (other_source, other, other_value))| {
+								j != i
+									&& other.op == ModifierOp::Add
+									&& other.stacking == Stacking::Source
+									&& other_source == source && other_value.signum() == value.signum()
+									&& (other_value.abs() > value.abs()
+										|| (other_value.abs() == value.abs() && j < i))
+							},
+						),
+					},
+				};
+				(source, modifier, value, applied)
+			})
+	}
+
+	/// Every modifier on `target` whose condition holds in `context`, with whether it
+	/// applies under the stacking rules.
+	pub fn active(
+		&self,
+		character: &Character,
+		target: impl Into<ModifierTarget>,
+		context: &RollContext,
+	) -> Vec<ActiveModifier> {
+		self.resolve(character, &target.into(), context)
+			.map(|(source, modifier, value, applied)| ActiveModifier {
+				source: source.clone(),
+				op: modifier.op,
+				value,
+				condition: modifier.condition.clone(),
+				applied,
+			})
+			.collect()
+	}
+
+	/// Applies the modifiers on `target` to a `base` value.
+	///
+	/// A `Set` replaces the base, then `Add` modifiers are summed, the sum is divided by
+	/// any `Div` modifiers and the result is clamped by any `Min` and `Max` modifiers.
+	/// Swaps are left to [`Modifiers::get_pool`], as `base` already includes them.
+	pub fn apply(
+		&self,
+		character: &Character,
+		target: impl Into<ModifierTarget>,
+		base: u16,
+		context: &RollContext,
+	) -> u16 {
+		let applied = self
+			.resolve(character, &target.into(), context)
+			.filter(|(.., applied)| *applied)
+			.map(|(_, modifier, value, _)| (modifier.op, value));
+
+		let base = applied
+			.clone()
+			.find(|(op, _)| *op == ModifierOp::Set)
+			.map_or(base, |(_, value)| add(0, value));
+		let mut value = add(
+			base,
+			applied
+				.clone()
+				.filter(|(op, _)| *op == ModifierOp::Add)
+				.map(|(_, value)| value)
+				.sum(),
+		);
+
+		for (_, divisor) in applied.clone().filter(|(op, _)| *op == ModifierOp::Div) {
+			value /= add(0, divisor).max(1);
+		}
+		for (op, limit) in applied {
+			match op {
+				ModifierOp::Min => value = value.max(add(0, limit)),
+				ModifierOp::Max => value = value.min(add(0, limit)),
+				_ => {}
+			}
+		}
+
+		value
+	}
+
+	/// Sum of the `Add` modifiers on `target` that apply outside of any roll.
+	pub fn get_modifier(&self, character: &Character, target: impl Into<ModifierTarget>) -> i16 {
+		self.resolve(character, &target.into(), &RollContext::default())
+			.filter(|(_, modifier, _, applied)| *applied && modifier.op == ModifierOp::Add)
+			.map(|(_, _, value, _)| value)
+			.sum()
+	}
+
+	/// Sum of the conditional `Add` modifiers on `target` that apply in `context`,
+	/// `None` if none do.
+	pub fn get_conditional_modifier(
+		&self,
+		character: &Character,
+		target: impl Into<ModifierTarget>,
+		context: &RollContext,
+	) -> Option<i16> {
+		self.resolve(character, &target.into(), context)
+			.filter(|(_, modifier, _, applied)| {
+				*applied && modifier.op == ModifierOp::Add && modifier.condition.is_some()
+			})
+			.map(|(_, _, value, _)| value)
+			.reduce(|acc, value| acc + value)
+	}
+
+	pub fn get_pool(
+		&self,
+		character: &Character,
+		target: impl Into<ModifierTarget>,
+	) -> Option<DicePool> {
+		self.get_conditional_pool(character, target, &RollContext::default())
+	}
+
+	pub fn get_conditional_pool(
+		&self,
+		character: &Character,
+		target: impl Into<ModifierTarget>,
+		context: &RollContext,
+	) -> Option<DicePool> {
+		let target = target.into();
+
+		match target {
+			ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Defense)) => {
+				let mut defense_attribute = DicePool::min(Attribute::Wits, Attribute::Dexterity);
+				let mut defense_skill = Skill::Athletics;
+
+				let modifiers =
+					self.entries(character, &target)
+						.iter()
+						.filter(|(_, _, modifier)| {
+							modifier
+								.condition
+								.as_ref()
+								.is_none_or(|condition| condition.check(character, context))
+						});
+
+				for (_, _, modifier) in modifiers {
+					if let ModifierValue::Skill(skill) = &modifier.value {
+						defense_skill = *skill;
+					} else if let ModifierValue::DicePool(pool) = &modifier.value {
+						defense_attribute = pool.clone();
+					}
+				}
+
+				Some(defense_attribute + defense_skill)
+			}
+			_ => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use cofd_schema::library::Library;
+
+	use super::*;
+	use crate::splat::vampire::{Discipline, Vampire};
+
+	#[test]
+	fn stacking() {
+		let character = Character::default();
+		let mut modifiers = Modifiers::new(&character);
+		let target = ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Speed));
+		let gear = |name: &str| ModifierSource::Equipment(name.to_owned());
+		let boots = |value: i16| {
+			Modifier::new(target.clone(), value, ModifierOp::Add).with_stacking(Stacking::Source)
+		};
+
+		let mut entries = vec![
+			(gear("Boots"), boots(1)),
+			(gear("Boots"), boots(2)),
+			(
+				ModifierSource::Template,
+				Modifier::new(target.clone(), 1, ModifierOp::Add),
+			),
+			(
+				ModifierSource::Template,
+				Modifier::conditional(target.clone(), 4, ModifierOp::Add, RollTag::Darkness),
+			),
+			(
+				ModifierSource::Template,
+				Modifier::new(target.clone(), 10, ModifierOp::Max),
+			),
+		];
+		modifiers.replace(Dependency::Template, entries.clone());
+
+		let context = RollContext::default();
+		let applied: Vec<_> = modifiers
+			.active(&character, target.clone(), &context)
+			.iter()
+			.map(|modifier| modifier.applied)
+			.collect();
+		assert_eq!(applied, [false, true, true, true]);
+		assert_eq!(modifiers.apply(&character, target.clone(), 7, &context), 10);
+		assert_eq!(modifiers.get_modifier(&character, target.clone()), 3);
+
+		let dark = RollContext::default().with_tag(RollTag::Darkness);
+		assert_eq!(
+			modifiers.get_conditional_modifier(&character, target.clone(), &dark),
+			Some(4)
+		);
+
+		entries.push((
+			gear("Cast"),
+			Modifier::new(target.clone(), 2, ModifierOp::Set).with_priority(1),
+		));
+		modifiers.replace(Dependency::Template, entries);
+		assert_eq!(modifiers.apply(&character, target.clone(), 7, &context), 2);
+
+		modifiers.update(&character);
+		assert_eq!(modifiers.apply(&character, target, 7, &context), 7);
+	}
+
+	#[test]
+	fn invalidate() {
+		let resilience = Ability::from(Discipline::Resilience);
+		let mut character = Character::builder()
+			.with_splat(Vampire::default())
+			.with_abilities([(resilience.clone(), 2)])
+			.with_merits([(Merit::Giant, 2)])
+			.build();
+		assert_eq!(character.size(), 5);
+
+		character.set_merit_rating(0, 3, &Library::new()).unwrap();
+		assert_eq!(character.size(), 6);
+		assert_eq!(character.attributes().stamina, 3);
+
+		character.add_ability(resilience.clone(), 3);
+		assert_eq!(character.attributes().stamina, 4);
+		character.remove_ability(&resilience);
+		assert_eq!(character.attributes().stamina, 1);
+		assert_eq!(character.size(), 6);
+
+		let mut character: Character = ron::from_str(&ron::to_string(&character).unwrap()).unwrap();
+		assert!(!character.modifiers.is_calculated());
+		assert_eq!(character.size(), 6);
+
+		character.get_merit_mut(0).unwrap().1 = 2;
+		assert_eq!(character.size(), 5);
+	}
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DicePool, DicePoolExt, DieRng, Roll, RollQualities};
+use crate::character::Character;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+	Actor,
+	Opponent,
+}
+
+/// Both sides roll their own pool, the side with more successes wins.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContestedRoll {
+	pub actor: Roll,
+	pub opponent: Roll,
+}
+
+impl ContestedRoll {
+	pub fn new(actor: Roll, opponent: Roll) -> Self {
+		Self { actor, opponent }
+	}
+
+	/// Rolls `actor_pool` for `actor` and `opponent_pool` for `opponent`.
+	///
+	/// Only the actor's roll benefits from `qualities`.
+	pub fn roll(
+		actor_pool: &DicePool,
+		actor: &Character,
+		opponent_pool: &DicePool,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Self {
+		Self::new(
+			actor_pool.roll(actor, qualities, rng),
+			opponent_pool.roll(opponent, RollQualities::default(), rng),
+		)
+	}
+
+	/// The side with the most successes, ties go to the actor.
+	///
+	/// Nobody wins if neither side rolled a success.
+	pub fn winner(&self) -> Option<Side> {
+		let actor = self.actor.successes();
+		let opponent = self.opponent.successes();
+
+		if actor == 0 && opponent == 0 {
+			None
+		} else if actor >= opponent {
+			Some(Side::Actor)
+		} else {
+			Some(Side::Opponent)
+		}
+	}
+}
+
+/// The opponent's resistance is subtracted from the actor's pool before rolling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResistedRoll {
+	/// The actor's pool, before resistance.
+	pub pool: i16,
+	/// The opponent's resistance trait.
+	pub resistance: i16,
+	pub roll: Roll,
+}
+
+impl ResistedRoll {
+	pub fn roll(
+		pool: &DicePool,
+		actor: &Character,
+		resistance: &DicePool,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Self {
+		let pool = i16::from(pool.value(actor));
+		let resistance = i16::from(resistance.value(opponent));
+
+		Self {
+			pool,
+			resistance,
+			roll: Roll::new(pool - resistance, qualities, rng),
+		}
+	}
+
+	/// The actor wins on any success, otherwise the resistance holds.
+	pub fn winner(&self) -> Side {
+		if self.roll.is_success() {
+			Side::Actor
+		} else {
+			Side::Opponent
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		dice_pool::roll::ReplayDice,
+		prelude::{Attribute, Attributes, Skill, Skills},
+	};
+
+	#[test]
+	fn contested_tie_goes_to_actor() {
+		let character = Character::builder()
+			.with_attributes(Attributes {
+				presence: 2,
+				..Default::default()
+			})
+			.with_skills(Skills {
+				intimidation: 1,
+				..Default::default()
+			})
+			.build();
+
+		let pool = (Attribute::Presence + Skill::Intimidation) - Attribute::Composure;
+		let contested = DicePool::vs(
+			Attribute::Presence + Skill::Intimidation,
+			Attribute::Composure + Skill::Empathy,
+		);
+
+		let roll = contested
+			.roll_contested(
+				&character,
+				&character,
+				RollQualities::default(),
+				&mut ReplayDice::new([8, 1, 1, 9]),
+			)
+			.unwrap();
+		assert_eq!(roll.actor.successes(), 1);
+		assert_eq!(roll.opponent.successes(), 1);
+		assert_eq!(roll.winner(), Some(Side::Actor));
+
+		let roll = pool
+			.roll_resisted(
+				&character,
+				&character,
+				RollQualities::default(),
+				&mut ReplayDice::new([8, 1]),
+			)
+			.unwrap();
+		assert_eq!(roll.roll.pool, 2);
+		assert_eq!(roll.winner(), Side::Actor);
+
+		let penalty = (Attribute::Presence + Skill::Intimidation) - DicePool::Mod(2);
+		assert!(
+			penalty
+				.roll_resisted(
+					&character,
+					&character,
+					RollQualities::default(),
+					&mut ReplayDice::new([8]),
+				)
+				.is_none()
+		);
+	}
+}
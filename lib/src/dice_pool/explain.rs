@@ -0,0 +1,432 @@
+use std::fmt;
+
+use cofd_schema::traits::Trait as SchemaTrait;
+use cofd_util::VariantName;
+use serde::Serialize;
+
+use super::{DicePool, DicePoolExt};
+use crate::{
+	character::{
+		Character,
+		modifier::{ModifierOp, ModifierSource, ModifierTarget, RollContext},
+	},
+	traits::Trait,
+};
+
+/// A [`Modifier`] that contributed to a trait.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AppliedModifier {
+	pub source: ModifierSource,
+	pub target: ModifierTarget,
+	pub op: ModifierOp,
+	pub value: i16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Summand {
+	pub negative: bool,
+	pub term: Term,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind")]
+pub enum TermKind {
+	Constant,
+	Trait {
+		#[serde(rename = "trait")]
+		trait_: SchemaTrait,
+		/// The dots bought for the trait, before any modifiers.
+		base: i16,
+		modifiers: Vec<AppliedModifier>,
+	},
+	Key {
+		key: String,
+		resolved: bool,
+	},
+	Lower {
+		terms: Box<[Term; 2]>,
+	},
+	Higher {
+		terms: Box<[Term; 2]>,
+	},
+	/// Flattened `Add`s and `Sub`s, with all constants folded into a single term.
+	Sum {
+		terms: Vec<Summand>,
+	},
+	Versus {
+		actor: Box<Term>,
+		opponent: DicePool,
+	},
+}
+
+/// A node of an explained dice pool, along with the value it contributes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Term {
+	pub value: i16,
+	#[serde(flatten)]
+	pub kind: TermKind,
+}
+
+/// Why a dice pool evaluates to what it does for a character.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+	pub term: Term,
+	pub wound_penalty: u16,
+	/// The pool after the wound penalty.
+	pub total: i16,
+}
+
+impl Explanation {
+	pub fn new(pool: &DicePool, character: &Character) -> Self {
+		let term = explain(pool, character);
+		let wound_penalty = character.wound_penalty();
+
+		Self {
+			total: term.value - i16::try_from(wound_penalty).unwrap_or(i16::MAX),
+			term,
+			wound_penalty,
+		}
+	}
+}
+
+fn explain(pool: &DicePool, character: &Character) -> Term {
+	match pool {
+		DicePool::Mod(value) => Term {
+			value: i16::from(*value),
+			kind: TermKind::Constant,
+		},
+		DicePool::Trait(trait_) => explain_trait(*trait_, character),
+		DicePool::Key(key) => {
+			let value = DicePoolExt::try_value(pool, character, character).ok();
+			Term {
+				value: value.map_or(0, i16::from),
+				kind: TermKind::Key {
+					key: key.clone(),
+					resolved: value.is_some(),
+				},
+			}
+		}
+		DicePool::Min(p1, p2) | DicePool::Max(p1, p2) => {
+			let terms = [explain(p1, character), explain(p2, character)];
+
+			let min = matches!(pool, DicePool::Min(..));
+			let value = if min {
+				terms[0].value.min(terms[1].value)
+			} else {
+				terms[0].value.max(terms[1].value)
+			};
+
+			let kind = if terms.iter().all(|term| term.kind == TermKind::Constant) {
+				TermKind::Constant
+			} else if min {
+				TermKind::Lower {
+					terms: Box::new(terms),
+				}
+			} else {
+				TermKind::Higher {
+					terms: Box::new(terms),
+				}
+			};
+
+			Term { value, kind }
+		}
+		DicePool::Add(_) | DicePool::Sub(..) => {
+			let mut terms = Vec::new();
+			let mut constant = 0;
+			flatten(pool, false, character, &mut terms, &mut constant);
+
+			if constant != 0 || terms.is_empty() {
+				terms.push(Summand {
+					negative: false,
+					term: Term {
+						value: constant,
+						kind: TermKind::Constant,
+					},
+				});
+			}
+
+			let value = terms
+				.iter()
+				.map(|s| {
+					if s.negative {
+						-s.term.value
+					} else {
+						s.term.value
+					}
+				})
+				.sum();
+
+			if let [
+				Summand {
+					negative: false, ..
+				},
+			] = terms.as_slice()
+			{
+				terms.pop().unwrap().term
+			} else {
+				Term {
+					value,
+					kind: TermKind::Sum { terms },
+				}
+			}
+		}
+		DicePool::Vs(actor, opponent) => {
+			let actor = explain(actor, character);
+			Term {
+				value: actor.value,
+				kind: TermKind::Versus {
+					actor: Box::new(actor),
+					opponent: (**opponent).clone(),
+				},
+			}
+		}
+	}
+}
+
+fn flatten(
+	pool: &DicePool,
+	negative: bool,
+	character: &Character,
+	terms: &mut Vec<Summand>,
+	constant: &mut i16,
+) {
+	match pool {
+		DicePool::Add(vec) => {
+			for pool in vec {
+				flatten(pool, negative, character, terms, constant);
+			}
+		}
+		DicePool::Sub(p1, p2) => {
+			flatten(p1, negative, character, terms, constant);
+			flatten(p2, !negative, character, terms, constant);
+		}
+		pool => {
+			let term = explain(pool, character);
+
+			if term.kind == TermKind::Constant {
+				*constant += if negative { -term.value } else { term.value };
+			} else {
+				terms.push(Summand { negative, term });
+			}
+		}
+	}
+}
+
+fn explain_trait(trait_: SchemaTrait, character: &Character) -> Term {
+	let value = i16::from(DicePool::Trait(trait_).value(character));
+
+	let (base, targets) = match trait_ {
+		SchemaTrait::Attribute(attr) => (
+			*character.base_attributes().get(&attr),
+			vec![
+				ModifierTarget::BaseAttribute(attr),
+				ModifierTarget::Attribute(attr),
+			],
+		),
+		SchemaTrait::Skill(skill) => (
+			character.base_skills().get(skill),
+			vec![
+				ModifierTarget::BaseSkill(skill),
+				ModifierTarget::Skill(skill),
+			],
+		),
+		SchemaTrait::DerivedTrait(dt) => (
+			character.base_derived_trait(dt),
+			vec![ModifierTarget::Trait(Trait::DerivedTrait(dt))],
+		),
+		SchemaTrait::SupernaturalTolerance(_) => (character.power, Vec::new()),
+	};
+
+	// Only the modifiers that count towards the value, as `Modifiers::apply` uses them.
+	let modifiers = targets
+		.into_iter()
+		.flat_map(|target| {
+			character
+				.active_modifiers(target.clone(), &RollContext::default())
+				.into_iter()
+				.filter(|modifier| modifier.applied)
+				.map(move |modifier| AppliedModifier {
+					source: modifier.source,
+					target: target.clone(),
+					op: modifier.op,
+					value: modifier.value,
+				})
+		})
+		.collect();
+
+	Term {
+		value,
+		kind: TermKind::Trait {
+			trait_,
+			base: i16::try_from(base).unwrap_or(i16::MAX),
+			modifiers,
+		},
+	}
+}
+
+impl Term {
+	fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize, sign: &str) -> fmt::Result {
+		let indent = "  ".repeat(depth);
+		write!(f, "{indent}{sign}{} ", self.value)?;
+
+		match &self.kind {
+			TermKind::Constant => writeln!(f, "modifier"),
+			TermKind::Trait {
+				trait_,
+				base,
+				modifiers,
+			} => {
+				writeln!(f, "{trait_} ({base} base)")?;
+				for modifier in modifiers {
+					if modifier.op == ModifierOp::Add {
+						writeln!(f, "{indent}    {:+} {}", modifier.value, modifier.source)?;
+					} else {
+						writeln!(
+							f,
+							"{indent}    {} {} {}",
+							modifier.op.name(),
+							modifier.value,
+							modifier.source
+						)?;
+					}
+				}
+				Ok(())
+			}
+			TermKind::Key { key, resolved } => {
+				if *resolved {
+					writeln!(f, "{key}")
+				} else {
+					writeln!(f, "{key} (unknown)")
+				}
+			}
+			TermKind::Lower { terms } | TermKind::Higher { terms } => {
+				let label = if matches!(self.kind, TermKind::Lower { .. }) {
+					"lower of"
+				} else {
+					"higher of"
+				};
+				writeln!(f, "{label}")?;
+				for term in terms.iter() {
+					term.fmt_indented(f, depth + 1, "")?;
+				}
+				Ok(())
+			}
+			TermKind::Sum { terms } => {
+				writeln!(f, "sum of")?;
+				for Summand { negative, term } in terms {
+					term.fmt_indented(f, depth + 1, if *negative { "-" } else { "+" })?;
+				}
+				Ok(())
+			}
+			TermKind::Versus { actor, opponent } => {
+				writeln!(f, "vs {opponent}")?;
+				actor.fmt_indented(f, depth + 1, "")
+			}
+		}
+	}
+}
+
+impl fmt::Display for Term {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.fmt_indented(f, 0, "")
+	}
+}
+
+impl fmt::Display for Explanation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.term)?;
+		if self.wound_penalty > 0 {
+			writeln!(f, "-{} wound penalty", self.wound_penalty)?;
+		}
+		writeln!(f, "= {}", self.total)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		character::conditions::Tilt,
+		prelude::{Attribute, Attributes, Skill, Skills},
+		splat::vampire::{Discipline, Vampire},
+		traits::DerivedTrait,
+	};
+
+	#[test]
+	fn explain_pool() {
+		let character = Character::builder()
+			.with_splat(Vampire::default())
+			.with_attributes(Attributes {
+				dexterity: 2,
+				..Default::default()
+			})
+			.with_skills(Skills {
+				athletics: 2,
+				..Default::default()
+			})
+			.with_abilities([(Discipline::Celerity.into(), 2)])
+			.build();
+
+		let pool = (Attribute::Dexterity + Skill::Athletics)
+			- (DicePool::Mod(1) + DicePool::Key("Celerity".to_owned()))
+			+ DicePool::Mod(3);
+		let explanation = pool.explain(&character);
+
+		assert_eq!(explanation.total, 5);
+		let TermKind::Sum { terms } = &explanation.term.kind else {
+			panic!("expected a sum, got {:?}", explanation.term);
+		};
+		assert_eq!(terms.len(), 4);
+		assert!(terms[2].negative);
+		assert_eq!(terms[3].term.value, 2);
+
+		let TermKind::Trait {
+			base, modifiers, ..
+		} = &terms[0].term.kind
+		else {
+			panic!("expected a trait, got {:?}", terms[0].term);
+		};
+		assert_eq!(*base, 2);
+		assert_eq!(
+			modifiers,
+			&[AppliedModifier {
+				source: ModifierSource::Template,
+				target: ModifierTarget::BaseAttribute(Attribute::Dexterity),
+				op: ModifierOp::Add,
+				value: 1,
+			}]
+		);
+	}
+
+	#[test]
+	fn explain_derived_trait() {
+		let mut character = Character::builder()
+			.with_attributes(Attributes {
+				dexterity: 3,
+				strength: 2,
+				..Default::default()
+			})
+			.build();
+		character.add_tilt(Tilt::LegWrack);
+
+		let explanation = DicePool::from(DerivedTrait::Speed).explain(&character);
+		assert_eq!(explanation.total, 5);
+
+		let TermKind::Trait {
+			base, modifiers, ..
+		} = &explanation.term.kind
+		else {
+			panic!("expected a trait, got {:?}", explanation.term);
+		};
+		assert_eq!(*base, 10);
+		assert_eq!(
+			modifiers,
+			&[AppliedModifier {
+				source: ModifierSource::Tilt(Tilt::LegWrack),
+				target: ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Speed)),
+				op: ModifierOp::Div,
+				value: 2,
+			}]
+		);
+	}
+}
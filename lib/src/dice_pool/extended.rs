@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{DicePool, DicePoolExt, DieRng, Roll, RollOutcome, RollQualities};
+use crate::character::Character;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtendedStatus {
+	#[default]
+	InProgress,
+	Succeeded,
+	/// Ran out of rolls before reaching the target.
+	Failed,
+	Abandoned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedRoll {
+	/// Situational modifier applied to this roll only.
+	pub modifier: i16,
+	/// Accumulated penalty from earlier failures.
+	pub penalty: i16,
+	pub roll: Roll,
+}
+
+impl ExtendedRoll {
+	pub fn outcome(&self) -> RollOutcome {
+		self.roll.outcome()
+	}
+}
+
+/// An extended action, accumulating successes over several rolls.
+///
+/// The number of rolls is capped at the base dice pool, every failure imposes a
+/// cumulative -1 on later rolls and a dramatic failure loses all accumulated successes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedAction {
+	pub pool: DicePool,
+	pub qualities: RollQualities,
+	pub target: u16,
+	/// Time taken by each roll.
+	pub interval: Duration,
+	pub max_rolls: u16,
+
+	successes: u16,
+	penalty: i16,
+	rolls: Vec<ExtendedRoll>,
+	status: ExtendedStatus,
+}
+
+impl ExtendedAction {
+	pub fn new(pool: DicePool, target: u16, interval: Duration, character: &Character) -> Self {
+		let max_rolls = u16::try_from(pool.value(character)).unwrap_or(0).max(1);
+
+		Self {
+			pool,
+			qualities: RollQualities::default(),
+			target,
+			interval,
+			max_rolls,
+			successes: 0,
+			penalty: 0,
+			rolls: Vec::new(),
+			status: ExtendedStatus::default(),
+		}
+	}
+
+	#[must_use]
+	pub fn with_qualities(mut self, qualities: RollQualities) -> Self {
+		self.qualities = qualities;
+		self
+	}
+
+	#[must_use]
+	pub fn with_max_rolls(mut self, max_rolls: u16) -> Self {
+		self.max_rolls = max_rolls;
+		self
+	}
+
+	/// Makes the next roll, `None` if the action is already resolved.
+	pub fn roll(
+		&mut self,
+		character: &Character,
+		modifier: i16,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Option<&ExtendedRoll> {
+		if self.is_resolved() {
+			return None;
+		}
+
+		let dice = i16::from(self.pool.value(character)) + modifier - self.penalty;
+		let roll = ExtendedRoll {
+			modifier,
+			penalty: self.penalty,
+			roll: Roll::new(dice, self.qualities, rng),
+		};
+
+		match roll.outcome() {
+			RollOutcome::ExceptionalSuccess | RollOutcome::Success => {
+				self.successes = self.successes.saturating_add(roll.roll.successes());
+			}
+			RollOutcome::Failure => self.penalty += 1,
+			RollOutcome::DramaticFailure => {
+				self.successes = 0;
+				self.penalty += 1;
+			}
+		}
+		self.rolls.push(roll);
+
+		if self.successes >= self.target {
+			self.status = ExtendedStatus::Succeeded;
+		} else if self.rolls_used() >= self.max_rolls {
+			self.status = ExtendedStatus::Failed;
+		}
+
+		self.rolls.last()
+	}
+
+	pub fn abandon(&mut self) {
+		if !self.is_resolved() {
+			self.status = ExtendedStatus::Abandoned;
+		}
+	}
+
+	pub fn status(&self) -> ExtendedStatus {
+		self.status
+	}
+
+	pub fn is_resolved(&self) -> bool {
+		self.status != ExtendedStatus::InProgress
+	}
+
+	pub fn successes(&self) -> u16 {
+		self.successes
+	}
+
+	pub fn remaining_successes(&self) -> u16 {
+		self.target.saturating_sub(self.successes)
+	}
+
+	pub fn penalty(&self) -> i16 {
+		self.penalty
+	}
+
+	pub fn rolls(&self) -> &[ExtendedRoll] {
+		&self.rolls
+	}
+
+	pub fn rolls_used(&self) -> u16 {
+		u16::try_from(self.rolls.len()).unwrap_or(u16::MAX)
+	}
+
+	pub fn rolls_left(&self) -> u16 {
+		self.max_rolls.saturating_sub(self.rolls_used())
+	}
+
+	/// Time spent on the action so far.
+	pub fn elapsed(&self) -> Duration {
+		self.interval * u32::from(self.rolls_used())
+	}
+
+	pub fn exceptional_successes(&self) -> usize {
+		self.count(RollOutcome::ExceptionalSuccess)
+	}
+
+	pub fn dramatic_failures(&self) -> usize {
+		self.count(RollOutcome::DramaticFailure)
+	}
+
+	fn count(&self, outcome: RollOutcome) -> usize {
+		self.rolls
+			.iter()
+			.filter(|roll| roll.outcome() == outcome)
+			.count()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		dice_pool::roll::ReplayDice,
+		prelude::{Attribute, Skill, Skills},
+	};
+
+	#[test]
+	fn extended_action() {
+		let character = Character::builder()
+			.with_skills(Skills {
+				investigation: 1,
+				..Default::default()
+			})
+			.build();
+
+		let mut action = ExtendedAction::new(
+			Attribute::Intelligence + Skill::Investigation,
+			3,
+			Duration::from_mins(30),
+			&character,
+		);
+		assert_eq!(action.max_rolls, 2);
+
+		let mut dice = ReplayDice::new([8, 1, 1, 2, 3]);
+
+		action.roll(&character, 0, &mut dice);
+		assert_eq!(action.successes(), 1);
+
+		action.roll(&character, 1, &mut dice);
+		assert_eq!(action.status(), ExtendedStatus::Failed);
+		assert_eq!(action.successes(), 1);
+		assert_eq!(action.penalty(), 1);
+		assert_eq!(action.elapsed(), Duration::from_hours(1));
+		assert!(action.roll(&character, 0, &mut dice).is_none());
+
+		let ron = ron::to_string(&action).unwrap();
+		assert_eq!(ron::from_str::<ExtendedAction>(&ron).unwrap(), action);
+	}
+}
@@ -0,0 +1,183 @@
+use std::cmp::{max, min};
+
+pub use cofd_schema::dice_pool::DicePool;
+
+use crate::{character::Character, traits::Trait};
+
+pub mod contest;
+pub mod explain;
+pub mod extended;
+pub mod odds;
+pub mod prepare;
+pub mod resolver;
+pub mod roll;
+
+pub use contest::{ContestedRoll, ResistedRoll, Side};
+pub use explain::Explanation;
+pub use extended::{ExtendedAction, ExtendedStatus};
+pub use odds::Odds;
+pub use prepare::{NoWillpower, PreparedResistance, PreparedRoll};
+pub use resolver::{ExtraKeys, KeyResolver, UnresolvedKeys};
+pub use roll::{Again, DieRng, Roll, RollOutcome, RollQualities};
+
+pub trait DicePoolExt {
+	/// Evaluates the pool, unresolvable keys count as 0.
+	fn value(&self, character: &Character) -> i8 {
+		self.value_with(character, character, &mut Vec::new())
+	}
+
+	/// Evaluates the pool, failing with every key neither the character nor `resolver`
+	/// could resolve.
+	///
+	/// # Errors
+	/// If any [`DicePool::Key`] can't be resolved.
+	fn try_value(
+		&self,
+		character: &Character,
+		resolver: &(impl KeyResolver + ?Sized),
+	) -> Result<i8, UnresolvedKeys> {
+		let mut unresolved = Vec::new();
+		let value = self.value_with(character, resolver, &mut unresolved);
+
+		if unresolved.is_empty() {
+			Ok(value)
+		} else {
+			Err(UnresolvedKeys(unresolved))
+		}
+	}
+
+	fn value_with(
+		&self,
+		character: &Character,
+		resolver: &(impl KeyResolver + ?Sized),
+		unresolved: &mut Vec<String>,
+	) -> i8;
+
+	/// Breaks the pool down into the values and modifiers it's made of.
+	fn explain(&self, character: &Character) -> Explanation;
+
+	fn roll(
+		&self,
+		character: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Roll {
+		Roll::new(i16::from(self.value(character)), qualities, rng)
+	}
+
+	/// Sets up a roll of the pool, to add modifiers and Willpower to before committing it.
+	fn prepare(&self, character: &Character) -> PreparedRoll;
+
+	/// Exact odds of rolling the pool, matching [`DicePoolExt::value`].
+	fn odds(&self, character: &Character, qualities: RollQualities) -> Odds {
+		Odds::new(i16::from(self.value(character)), qualities)
+	}
+
+	/// Rolls an `X vs Y` pool, `None` if the pool isn't contested.
+	fn roll_contested(
+		&self,
+		actor: &Character,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Option<ContestedRoll>;
+
+	/// Rolls an `X - Y` pool where `Y` is the opponent's resistance, `None` if the pool
+	/// isn't resisted.
+	fn roll_resisted(
+		&self,
+		actor: &Character,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Option<ResistedRoll>;
+}
+
+impl DicePoolExt for DicePool {
+	fn value_with(
+		&self,
+		character: &Character,
+		resolver: &(impl KeyResolver + ?Sized),
+		unresolved: &mut Vec<String>,
+	) -> i8 {
+		match self {
+			Self::Mod(val) => *val,
+
+			Self::Trait(trait_) => match trait_ {
+				cofd_schema::traits::Trait::DerivedTrait(dt) => {
+					character.get_trait(&Trait::DerivedTrait(*dt)) as i8
+				}
+				cofd_schema::traits::Trait::Attribute(attr) => {
+					character.get_trait(&Trait::Attribute(*attr)) as i8
+				}
+
+				cofd_schema::traits::Trait::Skill(skill) => {
+					character.get_trait(&Trait::Skill(*skill)) as i8
+				}
+				cofd_schema::traits::Trait::SupernaturalTolerance(_) => character.power as i8,
+			},
+			Self::Max(p1, p2) => max(
+				p1.value_with(character, resolver, unresolved),
+				p2.value_with(character, resolver, unresolved),
+			),
+			Self::Min(p1, p2) => min(
+				p1.value_with(character, resolver, unresolved),
+				p2.value_with(character, resolver, unresolved),
+			),
+
+			Self::Add(vec) => vec.iter().fold(0, |acc, e| {
+				acc + e.value_with(character, resolver, unresolved)
+			}),
+			Self::Sub(p1, p2) => {
+				p1.value_with(character, resolver, unresolved)
+					- p2.value_with(character, resolver, unresolved)
+			}
+			// The actor's side of a contested roll.
+			Self::Vs(p1, _) => p1.value_with(character, resolver, unresolved),
+
+			Self::Key(key) => {
+				if let Some(value) = resolver
+					.resolve_key(key)
+					.or_else(|| character.resolve_key(key))
+				{
+					i8::try_from(value).unwrap_or(if value < 0 { i8::MIN } else { i8::MAX })
+				} else {
+					unresolved.push(key.clone());
+					0
+				}
+			}
+		}
+	}
+
+	fn explain(&self, character: &Character) -> Explanation {
+		Explanation::new(self, character)
+	}
+
+	fn prepare(&self, character: &Character) -> PreparedRoll {
+		PreparedRoll::new(self, character)
+	}
+
+	fn roll_contested(
+		&self,
+		actor: &Character,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Option<ContestedRoll> {
+		self.contested().map(|(actor_pool, opponent_pool)| {
+			ContestedRoll::roll(actor_pool, actor, opponent_pool, opponent, qualities, rng)
+		})
+	}
+
+	fn roll_resisted(
+		&self,
+		actor: &Character,
+		opponent: &Character,
+		qualities: RollQualities,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Option<ResistedRoll> {
+		self.resisted().map(|(pool, resistance)| {
+			ResistedRoll::roll(pool, actor, resistance, opponent, qualities, rng)
+		})
+	}
+}
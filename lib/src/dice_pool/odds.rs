@@ -0,0 +1,189 @@
+use super::{
+	RollQualities,
+	roll::{EXCEPTIONAL_SUCCESS, SUCCESS_THRESHOLD},
+};
+
+/// Probabilities below this are dropped from the tail of a distribution.
+const EPSILON: f64 = 1e-15;
+
+/// Exact success distribution of a roll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Odds {
+	pub pool: i16,
+	pub qualities: RollQualities,
+	/// Probability of rolling exactly `i` successes.
+	distribution: Vec<f64>,
+	dramatic_failure: f64,
+}
+
+impl Odds {
+	/// Computes the odds of a resolved pool, using a chance die if it is 0 or less.
+	pub fn new(pool: i16, qualities: RollQualities) -> Self {
+		let (distribution, dramatic_failure) = if pool <= 0 {
+			// A chance die only succeeds on a 10 and never explodes, rote rerolls anything else.
+			let (success, dramatic_failure) = if qualities.rote {
+				(0.1 + 0.9 * 0.1, 0.9 * 0.1)
+			} else {
+				(0.1, 0.1)
+			};
+			(vec![1.0 - success, success], dramatic_failure)
+		} else {
+			let die = die_distribution(qualities);
+			let mut distribution = vec![1.0];
+			for _ in 0..pool {
+				distribution = convolve(&distribution, &die);
+			}
+			(distribution, 0.0)
+		};
+
+		Self {
+			pool,
+			qualities,
+			distribution,
+			dramatic_failure,
+		}
+	}
+
+	/// Probability of rolling exactly `successes` successes.
+	pub fn probability(&self, successes: u16) -> f64 {
+		self.distribution
+			.get(usize::from(successes))
+			.copied()
+			.unwrap_or(0.0)
+	}
+
+	/// Probability of rolling at least `successes` successes.
+	pub fn at_least(&self, successes: u16) -> f64 {
+		self.distribution
+			.iter()
+			.skip(usize::from(successes))
+			.sum::<f64>()
+			.min(1.0)
+	}
+
+	#[allow(clippy::cast_precision_loss)]
+	pub fn expected_successes(&self) -> f64 {
+		self.distribution
+			.iter()
+			.enumerate()
+			.map(|(successes, p)| successes as f64 * p)
+			.sum()
+	}
+
+	pub fn success(&self) -> f64 {
+		self.at_least(1)
+	}
+
+	pub fn exceptional_success(&self) -> f64 {
+		self.at_least(EXCEPTIONAL_SUCCESS)
+	}
+
+	/// Probability of failing without it being a dramatic failure.
+	pub fn failure(&self) -> f64 {
+		self.probability(0) - self.dramatic_failure
+	}
+
+	pub fn dramatic_failure(&self) -> f64 {
+		self.dramatic_failure
+	}
+
+	pub fn distribution(&self) -> &[f64] {
+		&self.distribution
+	}
+}
+
+/// Success distribution of a single pool die, including its again and rote rerolls.
+fn die_distribution(qualities: RollQualities) -> Vec<f64> {
+	let success = f64::from(11 - SUCCESS_THRESHOLD) / 10.0;
+	let fail = 1.0 - success;
+	let explode = qualities
+		.again
+		.threshold()
+		.map_or(0.0, |threshold| f64::from(11 - threshold) / 10.0);
+
+	// k successes: k - 1 explosions ending on a plain success, or k explosions ending on a failure.
+	let mut die = vec![fail];
+	let mut chain = 1.0;
+	while chain > EPSILON {
+		die.push(chain * (success - explode) + chain * explode * fail);
+		chain *= explode;
+	}
+
+	if qualities.rote {
+		// A failed die is rolled again, once.
+		let reroll = die.clone();
+		die[0] = 0.0;
+		for (p, reroll) in die.iter_mut().zip(reroll) {
+			*p += fail * reroll;
+		}
+	}
+
+	die
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+	let mut result = vec![0.0; a.len() + b.len() - 1];
+	for (i, a) in a.iter().enumerate() {
+		for (j, b) in b.iter().enumerate() {
+			result[i + j] += a * b;
+		}
+	}
+
+	while result.len() > 1 && result.last().is_some_and(|p| *p < EPSILON) {
+		result.pop();
+	}
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dice_pool::Again;
+
+	fn approx(a: f64, b: f64) -> bool {
+		(a - b).abs() < 1e-9
+	}
+
+	#[test]
+	fn single_die() {
+		let odds = Odds::new(1, RollQualities::default());
+		assert!(approx(odds.success(), 0.3));
+		assert!(approx(odds.expected_successes(), 1.0 / 3.0));
+
+		let odds = Odds::new(1, RollQualities::default().with_again(Again::EightAgain));
+		assert!(approx(odds.expected_successes(), 0.3 / 0.7));
+
+		let odds = Odds::new(
+			1,
+			RollQualities::default()
+				.with_again(Again::NoAgain)
+				.with_rote(),
+		);
+		assert!(approx(odds.success(), 0.51));
+		assert!(approx(odds.distribution().iter().sum(), 1.0));
+	}
+
+	#[test]
+	fn chance_die() {
+		let odds = Odds::new(0, RollQualities::default());
+		assert!(approx(odds.success(), 0.1));
+		assert!(approx(odds.dramatic_failure(), 0.1));
+		assert!(approx(odds.failure(), 0.8));
+
+		let odds = Odds::new(-2, RollQualities::default().with_rote());
+		assert!(approx(odds.success(), 0.19));
+		assert!(approx(odds.dramatic_failure(), 0.09));
+	}
+
+	#[test]
+	fn pool() {
+		let odds = Odds::new(5, RollQualities::default().with_again(Again::NoAgain));
+		assert!(approx(odds.exceptional_success(), 0.3_f64.powi(5)));
+		assert!(approx(odds.success(), 1.0 - 0.7_f64.powi(5)));
+		assert!(approx(odds.expected_successes(), 1.5));
+
+		let odds = Odds::new(7, RollQualities::default().with_rote());
+		assert!(approx(odds.distribution().iter().sum(), 1.0));
+		assert!(odds.at_least(3) > odds.at_least(4));
+	}
+}
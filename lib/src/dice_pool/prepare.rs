@@ -0,0 +1,225 @@
+use cofd_schema::modifiers::SuggestedModifiers;
+use thiserror::Error;
+
+use super::{DicePool, DicePoolExt, DieRng, Roll, RollQualities};
+use crate::character::{
+	Character,
+	modifier::{ModifierOp, ModifierTarget, RollContext},
+};
+
+/// Bonus dice from all sources combined can't exceed this.
+pub const MAX_BONUS_DICE: i16 = 5;
+/// Dice added by spending a Willpower point on a roll.
+pub const WILLPOWER_DICE: i16 = 3;
+/// Added to a resistance trait by spending a Willpower point.
+pub const WILLPOWER_RESISTANCE: i16 = 2;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("No Willpower left to spend")]
+pub struct NoWillpower;
+
+fn spend_willpower(character: &mut Character) -> Result<(), NoWillpower> {
+	character.willpower = character.willpower.checked_sub(1).ok_or(NoWillpower)?;
+	Ok(())
+}
+
+/// A roll being set up: the pool, situational modifiers and any Willpower spent.
+///
+/// Nothing is spent until the roll is committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedRoll {
+	/// The pool's value, before any modifiers.
+	pub base: i16,
+	pub wound_penalty: u16,
+	pub modifiers: Vec<(String, i16)>,
+	pub willpower: bool,
+	pub qualities: RollQualities,
+}
+
+impl PreparedRoll {
+	pub fn new(pool: &DicePool, character: &Character) -> Self {
+		Self::in_context(pool, character, &RollContext::default())
+	}
+
+	/// Sets up a roll made in `context`, with the character's modifiers to every roll
+	/// that hold in it, e.g. a Tilt's penalty.
+	pub fn in_context(pool: &DicePool, character: &Character, context: &RollContext) -> Self {
+		let modifiers = character
+			.active_modifiers(ModifierTarget::Roll, context)
+			.into_iter()
+			.filter(|modifier| modifier.applied && modifier.op == ModifierOp::Add)
+			.map(|modifier| (modifier.source.to_string(), modifier.value))
+			.collect();
+
+		Self {
+			base: i16::from(pool.value(character)),
+			wound_penalty: character.wound_penalty(),
+			modifiers,
+			willpower: false,
+			qualities: RollQualities::default(),
+		}
+	}
+
+	#[must_use]
+	pub fn with_modifier(mut self, name: impl Into<String>, value: i16) -> Self {
+		self.modifiers.push((name.into(), value));
+		self
+	}
+
+	/// Applies the suggested modifier called `name`, if there is one.
+	#[must_use]
+	pub fn with_suggested(self, suggested: &SuggestedModifiers, name: &str) -> Self {
+		match suggested.get(name) {
+			Some(value) => self.with_modifier(name, i16::from(value)),
+			None => self,
+		}
+	}
+
+	/// Spends a Willpower point for +3 dice.
+	#[must_use]
+	pub fn with_willpower(mut self) -> Self {
+		self.willpower = true;
+		self
+	}
+
+	#[must_use]
+	pub fn with_qualities(mut self, qualities: RollQualities) -> Self {
+		self.qualities = qualities;
+		self
+	}
+
+	/// Bonus dice from Willpower and positive modifiers, capped at [`MAX_BONUS_DICE`].
+	pub fn bonus(&self) -> i16 {
+		let willpower = if self.willpower { WILLPOWER_DICE } else { 0 };
+		let modifiers: i16 = self
+			.modifiers
+			.iter()
+			.map(|(_, value)| *value)
+			.filter(|value| *value > 0)
+			.sum();
+
+		(willpower + modifiers).min(MAX_BONUS_DICE)
+	}
+
+	/// Penalties from wounds and negative modifiers, as a positive number.
+	pub fn penalty(&self) -> i16 {
+		let modifiers: i16 = self
+			.modifiers
+			.iter()
+			.map(|(_, value)| *value)
+			.filter(|value| *value < 0)
+			.sum();
+
+		i16::try_from(self.wound_penalty).unwrap_or(i16::MAX) - modifiers
+	}
+
+	/// The number of dice that will be rolled, a chance die if 0 or less.
+	pub fn dice(&self) -> i16 {
+		self.base + self.bonus() - self.penalty()
+	}
+
+	/// Spends any Willpower and makes the roll.
+	///
+	/// # Errors
+	/// If Willpower is to be spent but the character has none left, in which case
+	/// nothing is rolled.
+	pub fn commit(
+		&self,
+		character: &mut Character,
+		rng: &mut (impl DieRng + ?Sized),
+	) -> Result<Roll, NoWillpower> {
+		if self.willpower {
+			spend_willpower(character)?;
+		}
+
+		Ok(Roll::new(self.dice(), self.qualities, rng))
+	}
+}
+
+/// A resistance trait such as Defense, optionally bolstered by a Willpower point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreparedResistance {
+	pub base: i16,
+	pub willpower: bool,
+}
+
+impl PreparedResistance {
+	pub fn new(resistance: &DicePool, character: &Character) -> Self {
+		Self {
+			base: i16::from(resistance.value(character)),
+			willpower: false,
+		}
+	}
+
+	/// Spends a Willpower point for +2 to the resistance.
+	#[must_use]
+	pub fn with_willpower(mut self) -> Self {
+		self.willpower = true;
+		self
+	}
+
+	pub fn value(&self) -> i16 {
+		if self.willpower {
+			self.base + WILLPOWER_RESISTANCE
+		} else {
+			self.base
+		}
+	}
+
+	/// Spends any Willpower and returns the final resistance.
+	///
+	/// # Errors
+	/// If Willpower is to be spent but the character has none left.
+	pub fn commit(&self, character: &mut Character) -> Result<i16, NoWillpower> {
+		if self.willpower {
+			spend_willpower(character)?;
+		}
+
+		Ok(self.value())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		character::Damage,
+		dice_pool::roll::ReplayDice,
+		prelude::{Attribute, Skill},
+		traits::DerivedTrait,
+	};
+
+	#[test]
+	fn willpower_and_modifiers() {
+		let mut character = Character::builder().build();
+		character.willpower = 1;
+		*character.health_mut() = Damage::new(0, 5, 0);
+
+		let suggested = "Equipment (+2), Distracted (-1), Blessed (+4)"
+			.parse::<SuggestedModifiers>()
+			.unwrap();
+		let roll = (Attribute::Strength + Skill::Brawl)
+			.prepare(&character)
+			.with_suggested(&suggested, "Equipment")
+			.with_suggested(&suggested, "Blessed")
+			.with_suggested(&suggested, "Distracted")
+			.with_willpower();
+
+		assert_eq!(roll.base, 1);
+		assert_eq!(roll.bonus(), MAX_BONUS_DICE);
+		assert_eq!(roll.penalty(), 3);
+		assert_eq!(roll.dice(), 3);
+		assert_eq!(character.willpower, 1);
+
+		let roll = roll
+			.commit(&mut character, &mut ReplayDice::new([1, 2, 3]))
+			.unwrap();
+		assert_eq!(roll.pool, 3);
+		assert_eq!(character.willpower, 0);
+
+		let resistance =
+			PreparedResistance::new(&DerivedTrait::Defense.into(), &character).with_willpower();
+		assert_eq!(resistance.value(), 3);
+		assert_eq!(resistance.commit(&mut character), Err(NoWillpower));
+	}
+}
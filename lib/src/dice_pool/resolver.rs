@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use cofd_util::VariantName;
+use thiserror::Error;
+
+use crate::{character::Character, template::Template};
+
+/// Resolves the free-form [`DicePool::Key`](super::DicePool::Key) terms of a dice pool.
+pub trait KeyResolver {
+	fn resolve_key(&self, key: &str) -> Option<i16>;
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unresolved dice pool keys: {}", .0.join(", "))]
+pub struct UnresolvedKeys(pub Vec<String>);
+
+/// Normalizes a key so "Blood Potency", "blood-potency" and `BloodPotency`'s
+/// [`VariantName`] all compare equal.
+pub fn normalize_key(key: &str) -> String {
+	key.trim()
+		.chars()
+		.filter(|c| !matches!(c, '\'' | '’'))
+		.map(|c| {
+			if c.is_whitespace() || c == '_' {
+				'-'
+			} else {
+				c.to_ascii_lowercase()
+			}
+		})
+		.collect()
+}
+
+fn saturate(value: u16) -> i16 {
+	i16::try_from(value).unwrap_or(i16::MAX)
+}
+
+impl KeyResolver for Character {
+	fn resolve_key(&self, key: &str) -> Option<i16> {
+		let key = normalize_key(key);
+		let template: &Template = &self.splat;
+
+		if key == "supernatural-tolerance"
+			|| template
+				.supernatural_tolerance()
+				.is_some_and(|st| normalize_key(st.as_ref()) == key)
+		{
+			return Some(saturate(self.power));
+		}
+		if key == "fuel"
+			|| template
+				.fuel()
+				.is_some_and(|fuel| normalize_key(fuel.as_ref()) == key)
+		{
+			return Some(saturate(self.fuel));
+		}
+		if key == "integrity" || normalize_key(template.integrity().as_ref()) == key {
+			return Some(saturate(self.integrity));
+		}
+
+		if let Some(value) = self
+			.abilities
+			.iter()
+			.filter(|(ability, _)| normalize_key(ability.name()) == key)
+			.map(|(_, value)| *value)
+			.max()
+		{
+			return Some(saturate(value));
+		}
+
+		self.merits
+			.iter()
+			.filter(|(merit, _)| normalize_key(merit.name()) == key)
+			.map(|(_, value)| saturate(*value))
+			.max()
+	}
+}
+
+/// Extra keys registered on top of another resolver, e.g. the Arcanum of a spell
+/// or the Renown of a gift.
+pub struct ExtraKeys<'a> {
+	inner: &'a dyn KeyResolver,
+	keys: HashMap<String, i16>,
+}
+
+impl<'a> ExtraKeys<'a> {
+	pub fn new(inner: &'a dyn KeyResolver) -> Self {
+		Self {
+			inner,
+			keys: HashMap::new(),
+		}
+	}
+
+	pub fn insert(&mut self, key: &str, value: i16) {
+		self.keys.insert(normalize_key(key), value);
+	}
+
+	#[must_use]
+	pub fn with_key(mut self, key: &str, value: i16) -> Self {
+		self.insert(key, value);
+		self
+	}
+}
+
+impl KeyResolver for ExtraKeys<'_> {
+	fn resolve_key(&self, key: &str) -> Option<i16> {
+		self.keys
+			.get(&normalize_key(key))
+			.copied()
+			.or_else(|| self.inner.resolve_key(key))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		dice_pool::{DicePool, DicePoolExt},
+		prelude::Attribute,
+		splat::vampire::{Discipline, Vampire},
+	};
+
+	#[test]
+	fn resolve_keys() {
+		let character = Character::builder()
+			// Daeva get a free dot of Dexterity.
+			.with_splat(Vampire::default())
+			.with_abilities([(Discipline::Celerity.into(), 2)])
+			.build();
+
+		let pool = Attribute::Dexterity
+			+ DicePool::Key("Celerity".to_owned())
+			+ DicePool::Key("Humanity".to_owned());
+		assert_eq!(pool.try_value(&character, &character), Ok(11));
+
+		let pool = pool + DicePool::Key("Arcanum".to_owned()) + DicePool::Key("Wyrd".to_owned());
+		assert_eq!(
+			pool.try_value(&character, &character),
+			Err(UnresolvedKeys(vec![
+				"Arcanum".to_owned(),
+				"Wyrd".to_owned()
+			]))
+		);
+		assert_eq!(pool.value(&character), 11);
+
+		let resolver = ExtraKeys::new(&character)
+			.with_key("Arcanum", 3)
+			.with_key("Wyrd", 0);
+		assert_eq!(pool.try_value(&character, &resolver), Ok(14));
+
+		let resolver = ExtraKeys::new(&character).with_key("Wyrd", -1000);
+		assert_eq!(
+			DicePool::Key("Wyrd".to_owned()).try_value(&character, &resolver),
+			Ok(i8::MIN)
+		);
+	}
+}
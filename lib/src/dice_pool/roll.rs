@@ -0,0 +1,313 @@
+use cofd_schema::item::ActionFields;
+pub use cofd_schema::item::RollOutcome;
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+/// Successes needed for an exceptional success.
+pub const EXCEPTIONAL_SUCCESS: u16 = 5;
+/// Lowest die face that counts as a success.
+pub const SUCCESS_THRESHOLD: u8 = 8;
+
+/// Source of d10 results.
+///
+/// Every [`RngCore`] is a die source, so rolls can be driven by a seeded RNG,
+/// while [`ReplayDice`] feeds back recorded faces to audit a past roll.
+pub trait DieRng {
+	fn roll_d10(&mut self) -> u8;
+}
+
+impl<R: RngCore + ?Sized> DieRng for R {
+	fn roll_d10(&mut self) -> u8 {
+		self.random_range(1..=10)
+	}
+}
+
+/// Replays a fixed sequence of die faces, e.g. the faces of a recorded [`Roll`].
+///
+/// # Panics
+/// Panics when asked for more dice than were recorded.
+#[derive(Debug, Clone)]
+pub struct ReplayDice {
+	faces: std::vec::IntoIter<u8>,
+}
+
+impl ReplayDice {
+	pub fn new(faces: impl Into<Vec<u8>>) -> Self {
+		Self {
+			faces: faces.into().into_iter(),
+		}
+	}
+}
+
+impl From<&Roll> for ReplayDice {
+	fn from(roll: &Roll) -> Self {
+		Self::new(roll.dice.iter().map(|die| die.face).collect::<Vec<_>>())
+	}
+}
+
+impl DieRng for ReplayDice {
+	fn roll_d10(&mut self) -> u8 {
+		self.faces.next().expect("ran out of recorded dice")
+	}
+}
+
+/// Creates a deterministic die source from a seed.
+pub fn seeded(seed: u64) -> StdRng {
+	StdRng::seed_from_u64(seed)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Again {
+	#[default]
+	TenAgain,
+	NineAgain,
+	EightAgain,
+	NoAgain,
+}
+
+impl Again {
+	/// Lowest face that adds another die, if any.
+	pub fn threshold(self) -> Option<u8> {
+		match self {
+			Again::TenAgain => Some(10),
+			Again::NineAgain => Some(9),
+			Again::EightAgain => Some(8),
+			Again::NoAgain => None,
+		}
+	}
+
+	pub fn explodes(self, face: u8) -> bool {
+		self.threshold().is_some_and(|threshold| face >= threshold)
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RollQualities {
+	pub again: Again,
+	pub rote: bool,
+}
+
+impl RollQualities {
+	#[must_use]
+	pub fn with_again(mut self, again: Again) -> Self {
+		self.again = again;
+		self
+	}
+
+	#[must_use]
+	pub fn with_rote(mut self) -> Self {
+		self.rote = true;
+		self
+	}
+}
+
+/// Why a die was rolled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DieKind {
+	/// A die from the pool itself.
+	Pool,
+	/// A chance die, rolled when the pool is reduced to 0 or less.
+	Chance,
+	/// A bonus die from a 10/9/8-again result.
+	Again,
+	/// A reroll of a failed pool die granted by the rote quality.
+	Rote,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Die {
+	pub face: u8,
+	pub kind: DieKind,
+}
+
+impl Die {
+	pub fn is_success(&self) -> bool {
+		match self.kind {
+			DieKind::Chance => self.face == 10,
+			_ => self.face >= SUCCESS_THRESHOLD,
+		}
+	}
+}
+
+/// The outcome of a single roll, with every die in the order it was rolled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Roll {
+	/// The resolved pool size, before falling back to a chance die.
+	pub pool: i16,
+	pub qualities: RollQualities,
+	pub dice: Vec<Die>,
+}
+
+impl Roll {
+	/// Rolls a resolved pool, using a chance die if it is 0 or less.
+	pub fn new(pool: i16, qualities: RollQualities, rng: &mut (impl DieRng + ?Sized)) -> Self {
+		let mut dice = Vec::new();
+
+		if pool <= 0 {
+			let face = rng.roll_d10();
+			dice.push(Die {
+				face,
+				kind: DieKind::Chance,
+			});
+
+			if qualities.rote && face != 10 {
+				dice.push(Die {
+					face: rng.roll_d10(),
+					kind: DieKind::Chance,
+				});
+			}
+		} else {
+			for _ in 0..pool {
+				roll_die(&mut dice, DieKind::Pool, qualities, rng);
+			}
+		}
+
+		Self {
+			pool,
+			qualities,
+			dice,
+		}
+	}
+
+	pub fn is_chance(&self) -> bool {
+		self.pool <= 0
+	}
+
+	pub fn successes(&self) -> u16 {
+		if self.is_chance() {
+			// A rote reroll replaces the failed chance die.
+			u16::from(self.dice.last().is_some_and(Die::is_success))
+		} else {
+			let successes = self.dice.iter().filter(|die| die.is_success()).count();
+			u16::try_from(successes).unwrap_or(u16::MAX)
+		}
+	}
+
+	pub fn is_success(&self) -> bool {
+		self.successes() > 0
+	}
+
+	pub fn is_exceptional(&self) -> bool {
+		self.successes() >= EXCEPTIONAL_SUCCESS
+	}
+
+	/// A chance die showing a 1 is a dramatic failure.
+	pub fn is_dramatic_failure(&self) -> bool {
+		self.is_chance() && self.dice.last().is_some_and(|die| die.face == 1)
+	}
+
+	pub fn outcome(&self) -> RollOutcome {
+		if self.is_exceptional() {
+			RollOutcome::ExceptionalSuccess
+		} else if self.is_success() {
+			RollOutcome::Success
+		} else if self.is_dramatic_failure() {
+			RollOutcome::DramaticFailure
+		} else {
+			RollOutcome::Failure
+		}
+	}
+
+	/// Rules text of a merit, gift facet or spell for this roll's outcome.
+	pub fn effect<'a>(&self, action: &'a ActionFields) -> &'a [String] {
+		action.effect(self.outcome())
+	}
+
+	/// Dice rolled on top of the pool through again or rote rules.
+	pub fn rerolls(&self) -> impl Iterator<Item = &Die> {
+		self.dice
+			.iter()
+			.filter(|die| matches!(die.kind, DieKind::Again | DieKind::Rote))
+	}
+}
+
+fn roll_die(
+	dice: &mut Vec<Die>,
+	kind: DieKind,
+	qualities: RollQualities,
+	rng: &mut (impl DieRng + ?Sized),
+) {
+	let mut face = rng.roll_d10();
+	dice.push(Die { face, kind });
+
+	if kind == DieKind::Pool && qualities.rote && face < SUCCESS_THRESHOLD {
+		face = rng.roll_d10();
+		dice.push(Die {
+			face,
+			kind: DieKind::Rote,
+		});
+	}
+
+	while qualities.again.explodes(face) {
+		face = rng.roll_d10();
+		dice.push(Die {
+			face,
+			kind: DieKind::Again,
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use cofd_schema::item::RollResults;
+
+	use super::*;
+
+	#[test]
+	fn again_and_rote() {
+		let qualities = RollQualities::default()
+			.with_again(Again::NineAgain)
+			.with_rote();
+		// 9 explodes into a 10 which explodes into a 3, 2 is rerolled into an 8.
+		let roll = Roll::new(2, qualities, &mut ReplayDice::new([9, 10, 3, 2, 8]));
+
+		assert_eq!(roll.dice.len(), 5);
+		assert_eq!(roll.successes(), 3);
+		assert_eq!(roll.rerolls().count(), 3);
+		assert!(!roll.is_exceptional());
+	}
+
+	#[test]
+	fn chance_die() {
+		let roll = Roll::new(-1, RollQualities::default(), &mut ReplayDice::new([10]));
+		assert!(roll.is_chance());
+		assert_eq!(roll.successes(), 1);
+		assert_eq!(roll.dice.len(), 1);
+
+		let roll = Roll::new(0, RollQualities::default(), &mut ReplayDice::new([9]));
+		assert!(!roll.is_success());
+		assert_eq!(roll.outcome(), RollOutcome::Failure);
+
+		let roll = Roll::new(0, RollQualities::default(), &mut ReplayDice::new([1]));
+		assert_eq!(roll.outcome(), RollOutcome::DramaticFailure);
+	}
+
+	#[test]
+	fn outcome_effect() {
+		let action = ActionFields {
+			roll_results: RollResults {
+				success: vec!["Success".to_owned()],
+				failure: vec!["Failure".to_owned()],
+				..Default::default()
+			},
+			..Default::default()
+		};
+
+		let roll = Roll::new(5, RollQualities::default(), &mut ReplayDice::new([8; 5]));
+		assert_eq!(roll.outcome(), RollOutcome::ExceptionalSuccess);
+		assert_eq!(roll.effect(&action), ["Success"]);
+
+		let roll = Roll::new(0, RollQualities::default(), &mut ReplayDice::new([1]));
+		assert_eq!(roll.effect(&action), ["Failure"]);
+	}
+
+	#[test]
+	fn seeded_replay() {
+		let qualities = RollQualities::default().with_again(Again::EightAgain);
+		let roll = Roll::new(8, qualities, &mut seeded(42));
+
+		assert_eq!(roll, Roll::new(8, qualities, &mut seeded(42)));
+		assert_eq!(roll, Roll::new(8, qualities, &mut ReplayDice::from(&roll)));
+	}
+}
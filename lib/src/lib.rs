@@ -15,9 +15,6 @@ pub mod dice_pool;
 pub mod splat;
 pub mod traits;
 
-#[macro_use]
-extern crate cofd_util;
-
 pub use cofd_schema::template;
 
 pub mod prelude {
@@ -1,10 +1,11 @@
+use cofd_schema::{
+	prerequisites::{PrerequisiteKey, Prerequisites},
+	template::Template,
+};
 use serde::{Deserialize, Serialize};
 
-use super::{Merit, NameKey, Splat, SplatTrait, XSplat, YSplat, ZSplat};
-use crate::{
-	character::{Character, Damage},
-	prelude::*,
-};
+use super::{Merit, NameKey, SplatTrait, XSplat, YSplat, ZSplat};
+use crate::{character::Damage, prelude::*};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(default)]
@@ -234,8 +235,9 @@ pub enum ChangelingMerit {
 }
 
 impl ChangelingMerit {
-	pub fn is_available(&self, character: &Character) -> bool {
-		matches!(character.splat, Splat::Changeling(..))
+	/// What a character needs to take the merit.
+	pub fn prerequisites(&self) -> Prerequisites {
+		vec![PrerequisiteKey::Template(Template::Changeling).into()].into()
 	}
 }
 
@@ -1,9 +1,13 @@
 pub use cofd_schema::template::mage::Arcanum;
+use cofd_schema::{
+	prerequisites::{PrerequisiteKey, Prerequisites},
+	template::Template,
+};
 use cofd_util::{AllVariants, VariantName};
 use serde::{Deserialize, Serialize};
 
-use super::{ability::Ability, Merit, Splat, SplatTrait, XSplat, YSplat, ZSplat};
-use crate::prelude::{Attribute, Character, Skill};
+use super::{Merit, SplatTrait, XSplat, YSplat, ZSplat, ability::Ability};
+use crate::prelude::{Attribute, Skill};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 #[serde(default)]
@@ -127,13 +131,7 @@ impl SplatTrait for Mage {
 	}
 
 	fn all_abilities(&self) -> Option<Vec<Ability>> {
-		Some(
-			Arcanum::all()
-				.iter()
-				.copied()
-				.map(Into::into)
-				.collect(),
-		)
+		Some(Arcanum::all().iter().copied().map(Into::into).collect())
 	}
 
 	fn alternate_beats_optional(&self) -> bool {
@@ -173,7 +171,7 @@ pub enum Path {
 }
 
 impl Path {
-	fn get_ruling_arcana(&self) -> &[Arcanum; 2] {
+	pub fn get_ruling_arcana(&self) -> &[Arcanum; 2] {
 		match self {
 			Path::Acanthus => &[Arcanum::Time, Arcanum::Fate],
 			Path::Mastigos => &[Arcanum::Space, Arcanum::Mind],
@@ -183,7 +181,7 @@ impl Path {
 			Path::Custom(_, ruling, _) => ruling,
 		}
 	}
-	fn get_inferior_arcanum(&self) -> &Arcanum {
+	pub fn get_inferior_arcanum(&self) -> &Arcanum {
 		match self {
 			Path::Acanthus => &Arcanum::Forces,
 			Path::Mastigos => &Arcanum::Matter,
@@ -266,8 +264,9 @@ pub enum MageMerit {
 }
 
 impl MageMerit {
-	pub fn is_available(&self, character: &Character) -> bool {
-		matches!(character.splat, Splat::Mage(..))
+	/// What a character needs to take the merit.
+	pub fn prerequisites(&self) -> Prerequisites {
+		vec![PrerequisiteKey::Template(Template::Mage).into()].into()
 	}
 }
 
@@ -1,4 +1,11 @@
-use cofd_schema::{prelude::Skill, traits::DerivedTrait};
+use cofd_schema::{
+	book::MeritItem,
+	item::merit::{MeritSubItem, MeritTag},
+	library::Library,
+	prelude::Skill,
+	prerequisites::{ChosenSkill, Prerequisites},
+	traits::DerivedTrait,
+};
 use cofd_util::{AllVariants, VariantName};
 use serde::{Deserialize, Serialize};
 
@@ -7,8 +14,11 @@ use super::{
 	werewolf::WerewolfMerit,
 };
 use crate::{
-	character::modifier::{Modifier, ModifierOp, ModifierTarget, ModifierValue},
-	prelude::{Attributes, Skills, Trait},
+	character::{
+		conditions::Condition,
+		modifier::{Modifier, ModifierOp, ModifierTarget, ModifierValue},
+	},
+	prelude::{Character, Trait},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash, AllVariants, VariantName)]
@@ -39,7 +49,7 @@ pub enum Merit {
 	ObjectFetishism(String),
 	Patient,
 	// RenownedArtisan(String) // MTC
-	Scarred(String), // TODO: Condition
+	Scarred(Condition),
 	ToleranceForBiology,
 	TrainedObserver,
 	ViceRidden(String),
@@ -127,37 +137,37 @@ pub enum Merit {
 	DefensiveCombat(bool, Option<Skill>), // Brawl / Weaponry
 
 	// Fighting Styles
-	// ArmedDefense,
-	// Avoidance,
-	// Berserker,
-	// Bowmanship,
-	// Boxing,
-	// BruteForce,
-	// ChainWeapons,
-	// CloseQuartersCombat,
-	// CombatArchery,
-	// DisablingTactics,
-	// Firefight,
-	// Grappling,
-	// HeavyWeapons,
-	// ImprovisedWeapons,
-	// KinoMutai,
-	// LightWeapons
-	// Marksmanship
-	// MaritalArts
-	// MountedCombat
-	// PoliceTactics
-	// PoweredProjectile
+	ArmedDefense,
+	Avoidance,
+	Berserker,
+	Bowmanship,
+	Boxing,
+	BruteForce,
+	ChainWeapons,
+	CloseQuartersCombat,
+	CombatArchery,
+	DisablingTactics,
+	Firefight,
+	Grappling,
+	HeavyWeapons,
+	ImprovisedWeapons,
+	KinoMutai,
+	LightWeapons,
+	Marksmanship,
+	MartialArts,
+	MountedCombat,
+	PoliceTactics,
+	PoweredProjectile,
 	RelentlessAssault,
-	// SpearAndBayonet
-	// StaffFighting,
-	// StreetFighting,
+	SpearAndBayonet,
+	StaffFighting,
+	StreetFighting,
 	// StrengthPerformance, // TODO: Give Giant?
-	// Systema,
-	// ThrownWepons,
-	// TwoWeaponFighting,
-	// UnarmedDefense,
-	// WeaponAndShield
+	Systema,
+	ThrownWeapons,
+	TwoWeaponFighting,
+	UnarmedDefense,
+	WeaponAndShield,
 	#[expand]
 	Mage(MageMerit),
 	#[expand]
@@ -198,7 +208,7 @@ impl Merit {
 			Self::ObjectFetishism(String::new()),
 			Self::Patient,
 			// RenownedArtisan(String) // MTC
-			Self::Scarred(String::new()),
+			Self::Scarred(Condition::default()),
 			Self::ToleranceForBiology,
 			Self::TrainedObserver,
 			Self::ViceRidden(String::new()),
@@ -270,6 +280,53 @@ impl Merit {
 		]
 	}
 
+	pub fn fighting_styles() -> Vec<Merit> {
+		vec![
+			Self::ArmedDefense,
+			Self::Avoidance,
+			Self::Berserker,
+			Self::Bowmanship,
+			Self::Boxing,
+			Self::BruteForce,
+			Self::ChainWeapons,
+			Self::CloseQuartersCombat,
+			Self::CombatArchery,
+			Self::DisablingTactics,
+			Self::Firefight,
+			Self::Grappling,
+			Self::HeavyWeapons,
+			Self::ImprovisedWeapons,
+			Self::KinoMutai,
+			Self::LightWeapons,
+			Self::Marksmanship,
+			Self::MartialArts,
+			Self::MountedCombat,
+			Self::PoliceTactics,
+			Self::PoweredProjectile,
+			Self::RelentlessAssault,
+			Self::SpearAndBayonet,
+			Self::StaffFighting,
+			Self::StreetFighting,
+			Self::Systema,
+			Self::ThrownWeapons,
+			Self::TwoWeaponFighting,
+			Self::UnarmedDefense,
+			Self::WeaponAndShield,
+		]
+	}
+
+	/// The universal merits listed under `tag`, e.g. [`Merit::fighting_styles`] for
+	/// [`MeritTag::Fighting`].
+	pub fn by_tag(tag: &MeritTag) -> Vec<Merit> {
+		match tag {
+			MeritTag::Mental => Self::mental(),
+			MeritTag::Physical => Self::physical(),
+			MeritTag::Social => Self::social(),
+			MeritTag::Fighting => Self::fighting_styles(),
+			_ => Vec::new(),
+		}
+	}
+
 	pub fn get_modifiers(&self, value: u16) -> Vec<Modifier> {
 		match &self {
 			Merit::DefensiveCombat(true, Some(skill)) => {
@@ -290,125 +347,90 @@ impl Merit {
 					vec![]
 				}
 			}
+			Merit::Scarred(condition) => condition.get_modifiers(),
 			Merit::Werewolf(merit) => merit.get_modifiers(value),
 			_ => vec![],
 		}
 	}
 
-	pub fn is_available(
+	/// The merit's item in `library`, found by name.
+	pub fn item<'a>(&self, library: &'a Library) -> Option<&'a MeritItem> {
+		library.merit(self.name())
+	}
+
+	/// The name of the merit's item in `library`, falling back to the merit's own name.
+	pub fn display_name(&self, library: &Library) -> String {
+		self.item(library)
+			.map_or_else(|| self.name().to_owned(), |item| item.name.clone())
+	}
+
+	/// Sub-items of the merit's item in `library` unlocked at `rating`, e.g. a Fighting
+	/// Style's maneuvers, leaving out those whose prerequisites the character doesn't meet.
+	pub fn maneuvers<'a>(
 		&self,
-		character: &crate::prelude::Character,
-		attributes: &Attributes,
-		skills: &Skills,
-	) -> bool {
+		rating: u16,
+		character: &Character,
+		library: &'a Library,
+	) -> Vec<&'a MeritSubItem> {
+		let rating = u8::try_from(rating).unwrap_or(u8::MAX);
+
+		self.item(library)
+			.into_iter()
+			.flat_map(|item| &item.inner.children)
+			.filter(|maneuver| {
+				maneuver.is_unlocked(rating) && maneuver.prerequisites.check(character).is_met()
+			})
+			.collect()
+	}
+
+	/// The merit whose rating this one's can't exceed, e.g. Library (Advanced) is capped
+	/// at the character's Safe Place.
+	pub fn capped_by(&self) -> Option<Merit> {
 		match self {
-			Merit::Custom(_) => true,
+			Self::LibraryAdvanced(_) => Some(Self::SafePlace(String::new())),
+			_ => None,
+		}
+	}
 
-			Self::AreaOfExpertise(_) => character.attributes().resolve > 1,
-			// Self::Anonymity => // No Fame
-			Self::EyeForTheStrange => {
-				character.attributes().resolve > 1 && character.skills().occult > 0
-			}
-			Self::FastReflexes => {
-				let attr = character.attributes();
-				attr.wits > 2 || attr.dexterity > 2
-			}
-			Self::GoodTimeManagement => {
-				let skills = character.skills();
-				skills.academics > 1 || skills.science > 1
-			}
-			Self::Indomitable => character.attributes().resolve > 2,
-			Self::InterdisciplinarySpecialty(_, Some(skill)) => character.skills().get(*skill) > 2,
-			Self::InvestigativeAide(Some(skill)) => character.skills().get(*skill) > 2,
-			Self::InvestigativeProdigy => {
-				character.attributes().wits > 2 && character.skills().investigation > 2
-			}
-			// Self::LibraryAdvanced() // Library 3 + <= Safe Place
-			Self::Scarred(_) => character.integrity <= 5,
-			Self::ToleranceForBiology => character.attributes().resolve > 2,
-			Self::TrainedObserver => {
-				let attrs = character.attributes();
-				attrs.wits > 2 || attrs.composure > 2
-			}
-			// Self::ViceRidden(_) if character.template.vice_anchor() != Anchor::Vice => false,
-			// Self::Virtuous(_) if character.template.virtue_anchor() != Anchor::Virtue => false,
+	/// The Skill the merit is taken for, which its "Any Skill" prerequisite applies to.
+	pub fn chosen_skill(&self) -> Option<Skill> {
+		match self {
+			Self::InterdisciplinarySpecialty(_, skill)
+			| Self::InvestigativeAide(skill)
+			| Self::HobbyistClique(_, skill) => *skill,
+			_ => None,
+		}
+	}
 
-			// Self::Ambidextrous // Character creation only
-			Self::AutomotiveGenius => {
-				let skills = character.skills();
-				skills.crafts > 2 && skills.drive > 0 && skills.science > 0
-			}
-			Self::CovertOperative => {
-				let attr = character.attributes();
-				attr.wits > 2 && attr.dexterity > 2 && character.skills().stealth > 1
-			}
-			Self::CrackDriver => character.skills().drive > 2,
-			Self::Demolisher => {
-				let attr = character.attributes();
-				attr.strength > 2 || attr.intelligence > 2
-			}
-			Self::DoubleJointed => character.attributes().dexterity > 2,
-			Self::FleetOfFoot => character.skills().athletics > 1,
-			Self::Freediving => character.skills().athletics > 1,
-			// Self::Giant // Character Creation OR Strength Performance
-			Self::Hardy => character.attributes().stamina > 2,
-			Self::Greyhound => {
-				let attr = character.attributes();
-				character.skills().athletics > 2 && attr.wits > 2 && attr.stamina > 2
-			}
-			// IronSkin
-			Self::IronStamina => {
-				let attr = character.attributes();
-				attr.stamina > 2 || attr.resolve > 2
-			}
-			Self::QuickDraw(_) => character.attributes().wits > 2,
-			Self::PunchDrunk => character.max_willpower() > 5,
-			Self::Relentless => {
-				character.skills().athletics > 1 && character.attributes().stamina > 2
-			}
-			// Self::Roadkill // Merit Dep Aggressive Driving 2
-			Self::SeizingTheEdge => {
-				let attr = character.attributes();
-				attr.wits > 2 && attr.composure > 2
-			}
-			Self::SleightOfHand => character.skills().larceny > 2,
-			// Self::SmallFramed // Character Creation
-			// Self::Survivalist => character.skills().survival > 2 // Iron Stamina 3 dependency
-			Self::AirOfMenace => character.skills().intimidation > 1,
-			// Self::Anonymity // No Fame Merit
-			Self::Barfly => character.skills().socialize > 1,
-			Self::ClosedBook => {
-				let attr = character.attributes();
-				attr.manipulation > 2 && attr.resolve > 2
-			}
-			Self::CohesiveUnit => character.attributes().presence > 2,
-			Self::Empath => character.skills().empathy > 1,
-			// Self::Fame // No Anonymity Merit
-			// Self::Fixer => character.attributes().wits > 2 // Contacts 2
-			Self::HobbyistClique(_, Some(skill)) => character.skills().get(*skill) > 1,
-			Self::Inspiring => character.attributes().presence > 2,
-			Self::IronWill => character.attributes().resolve > 3,
-			Self::Peacemaker => character.attributes().wits > 2 && character.skills().empathy > 2,
-			Self::Pusher => character.skills().persuasion > 1,
-			Self::SmallUnitTactics => character.attributes().presence > 2,
-			Self::SpinDoctor => {
-				character.attributes().manipulation > 2 && character.skills().subterfuge > 1
-			}
-			Self::TableTurner => {
-				let attr = character.attributes();
-				attr.composure > 2 && attr.manipulation > 2 && attr.wits > 2
-			}
-			// Self::TakesOneToKnowOne if character.template.vice_anchor() != Anchor::Vice => false,
-			Self::Taste(_, _) => character.skills().crafts > 1,
-			Self::Untouchable => {
-				character.attributes().manipulation > 2 && character.skills().subterfuge > 1
-			}
+	/// Prerequisites of template merits that aren't defined by book data.
+	fn builtin_prerequisites(&self) -> Prerequisites {
+		match self {
+			Self::Mage(merit) => merit.prerequisites(),
+			Self::Vampire(merit) => merit.prerequisites(),
+			Self::Werewolf(merit) => merit.prerequisites(),
+			Self::Changeling(merit) => merit.prerequisites(),
+			_ => Prerequisites::default(),
+		}
+	}
+
+	/// Whether the character can take the merit.
+	///
+	/// Prerequisites come from the merit's item in `library`, or for a template merit the
+	/// library doesn't define, from the merit itself. Any other merit the library doesn't
+	/// define, e.g. because its book isn't loaded, is treated as having no prerequisites.
+	/// Use [`Merit::item`] to tell whether the library defines the merit.
+	pub fn is_available(&self, character: &Character, library: &Library) -> bool {
+		let builtin;
+		let prerequisites = if let Some(item) = self.item(library) {
+			&item.inner.prerequisites
+		} else {
+			builtin = self.builtin_prerequisites();
+			&builtin
+		};
 
-			Self::Mage(merit) => merit.is_available(character),
-			Self::Vampire(merit) => merit.is_available(character, attributes, skills),
-			Self::Werewolf(merit) => merit.is_available(character),
-			Self::Changeling(merit) => merit.is_available(character),
-			_ => true,
+		match self.chosen_skill() {
+			Some(skill) => prerequisites.check(&ChosenSkill(character, skill)).is_met(),
+			None => prerequisites.check(character).is_met(),
 		}
 	}
 }
@@ -428,3 +450,235 @@ impl From<Merit> for Ability {
 pub trait NameKey {
 	fn name_key(&self) -> String;
 }
+
+#[cfg(test)]
+mod tests {
+	use std::{str::FromStr, sync::Arc};
+
+	use chrono::NaiveDate;
+	use cofd_schema::prerequisites::Prerequisites;
+
+	use super::*;
+	use crate::{
+		character::modifier::BookModifiers,
+		prelude::{Attributes, Skills},
+		splat::vampire::{Clan, Discipline, Vampire},
+	};
+
+	fn library() -> Library {
+		let mut library = Library::new();
+		library.add_book(ron::from_str(include_str!("../../data/merits_universal.ron")).unwrap());
+		library
+	}
+
+	#[test]
+	fn merit_names() {
+		let library = library();
+
+		for merit in [
+			Merit::LibraryAdvanced(vec![]),
+			Merit::TakesOneToKnowOne,
+			Merit::AreaOfExpertise(String::new()),
+		] {
+			assert!(library.merit(merit.name()).is_some(), "{}", merit.name());
+		}
+	}
+
+	#[test]
+	fn merit_tags() {
+		let library = library();
+
+		for tag in [
+			MeritTag::Mental,
+			MeritTag::Physical,
+			MeritTag::Social,
+			MeritTag::Fighting,
+		] {
+			for merit in Merit::by_tag(&tag) {
+				if let Some(item) = merit.item(&library) {
+					assert!(item.inner.tags.contains(&tag), "{}", item.name);
+				}
+			}
+		}
+
+		let boxing = Merit::Boxing.item(&library).unwrap();
+		assert!(boxing.inner.tags.contains(&MeritTag::Style));
+		assert_eq!(boxing.inner.children.len(), 5);
+	}
+
+	#[test]
+	fn is_available() {
+		let library = library();
+		let character = Character::builder()
+			.with_attributes(Attributes {
+				wits: 3,
+				resolve: 3,
+				..Default::default()
+			})
+			.with_skills(Skills {
+				academics: 2,
+				..Default::default()
+			})
+			.build();
+
+		assert!(Merit::FastReflexes.is_available(&character, &library));
+		assert!(Merit::GoodTimeManagement.is_available(&character, &library));
+		assert!(Merit::Indomitable.is_available(&character, &library));
+		assert!(!Merit::IronWill.is_available(&character, &library));
+		assert!(!Merit::InvestigativeProdigy.is_available(&character, &library));
+		assert!(!Merit::PunchDrunk.is_available(&character, &library));
+		assert!(!Merit::Fixer.is_available(&character, &library));
+
+		assert!(
+			Merit::HobbyistClique(String::new(), Some(Skill::Academics))
+				.is_available(&character, &library)
+		);
+		assert!(
+			!Merit::InvestigativeAide(Some(Skill::Academics)).is_available(&character, &library)
+		);
+
+		// Without the merit's item, there are no prerequisites to check.
+		let empty = Library::new();
+		assert!(Merit::IronWill.is_available(&character, &empty));
+		assert!(Merit::InvestigativeAide(Some(Skill::Academics)).is_available(&character, &empty));
+	}
+
+	#[test]
+	fn merit_prerequisites() {
+		let library = library();
+		let mut character = Character::builder()
+			.with_splat(Vampire::default())
+			.with_abilities([(Discipline::Celerity.into(), 2)])
+			.with_attributes(Attributes {
+				wits: 3,
+				..Default::default()
+			})
+			.with_merits([
+				(Merit::Contacts(vec![]), 2),
+				(Merit::Fame, 1),
+				(Merit::Library(None), 3),
+			])
+			.build();
+
+		assert!(Merit::Fixer.is_available(&character, &library));
+		assert!(!Merit::Anonymity.is_available(&character, &library));
+		assert!(!Merit::LibraryAdvanced(vec![]).is_available(&character, &library));
+		assert!(!Merit::ViceRidden(String::new()).is_available(&character, &library));
+		assert!(!Merit::Vampire(VampireMerit::DreamVisions).is_available(&character, &library));
+		assert!(!Merit::Vampire(VampireMerit::ClawsOfTheUnholy).is_available(&character, &library));
+
+		let mekhet = Character::builder()
+			.with_splat(Vampire::new(Clan::Mekhet, None, None))
+			.build();
+		assert!(Merit::Vampire(VampireMerit::DreamVisions).is_available(&mekhet, &library));
+		assert!(!Merit::Vampire(VampireMerit::PackAlpha).is_available(&mekhet, &library));
+		assert!(
+			ron::from_str::<Prerequisites>(r#"[(Ability("Celerity"), 2), (BloodPotency, 1)]"#)
+				.unwrap()
+				.check(&character)
+				.is_met()
+		);
+		// Written as text, a Discipline reads as a merit the character doesn't have.
+		assert!(
+			!Prerequisites::from_str("Celerity ••")
+				.unwrap()
+				.check(&character)
+				.is_met()
+		);
+
+		character.add_merit(Merit::SafePlace(String::new()));
+		character.set_merit_rating(3, 1, &library).unwrap();
+		assert!(Merit::LibraryAdvanced(vec![]).is_available(&character, &library));
+
+		assert!(Merit::Ambidextrous.is_available(&character, &library));
+		character.award_beats(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), "Session", 1);
+		assert!(!Merit::Ambidextrous.is_available(&character, &library));
+	}
+
+	#[test]
+	fn maneuvers() {
+		let mut library = library();
+		library.add_book(
+			ron::from_str(
+				r#"(
+				info: (name: "Homebrew", id: Codex, hash: "0", publicationDate: "2024-01-01"),
+				merits: [
+					{
+						"name": "Street Fighting",
+						"reference": (Codex, 1),
+						"dotRating": (start: 1, end: 3),
+						"tags": [Fighting, Style],
+						"children": [
+							(
+								name: "Quick Hands",
+								description: [],
+								dotRating: 1,
+								modifiers: [(target: Trait(Initiative), value: Num(1))],
+							),
+							(
+								name: "Knocked Down",
+								description: [],
+								dotRating: 2,
+								prerequisites: [(Brawl, 3)],
+								modifiers: [(target: Trait(Speed), value: Num(2))],
+							),
+							(name: "Last Resort", description: [], dotRating: 3),
+						],
+					},
+				],
+			)"#,
+			)
+			.unwrap(),
+		);
+
+		let mut character = Character::builder()
+			.with_skills(Skills {
+				brawl: 2,
+				..Default::default()
+			})
+			.with_merits([(Merit::StreetFighting, 2)])
+			.with_book_modifiers(Arc::new(BookModifiers::from(&library)))
+			.build();
+		let names = |character: &Character| {
+			character
+				.maneuvers(&library)
+				.into_iter()
+				.map(|(_, maneuver)| maneuver.name.clone())
+				.collect::<Vec<_>>()
+		};
+		let speed = Character::builder().build().speed();
+		assert_eq!(names(&character), ["Quick Hands"]);
+		assert_eq!(
+			character.initiative(),
+			Character::builder().build().initiative() + 1
+		);
+		assert_eq!(character.speed(), speed);
+
+		character.base_skills_mut().brawl = 3;
+		assert_eq!(names(&character), ["Quick Hands", "Knocked Down"]);
+		assert_eq!(character.speed(), speed + 2);
+	}
+
+	#[test]
+	fn duck_and_weave() {
+		let library = library();
+		let mut character = Character::builder()
+			.with_attributes(Attributes {
+				dexterity: 2,
+				wits: 3,
+				..Default::default()
+			})
+			.with_skills(Skills {
+				athletics: 1,
+				brawl: 3,
+				..Default::default()
+			})
+			.with_merits([(Merit::Boxing, 1)])
+			.with_book_modifiers(Arc::new(BookModifiers::from(&library)))
+			.build();
+		assert_eq!(character.defense(), 3);
+
+		character.set_merit_rating(0, 2, &library).unwrap();
+		assert_eq!(character.defense(), 5);
+	}
+}
@@ -1,4 +1,4 @@
-use std::ops::Deref;
+use std::{ops::Deref, time::Duration};
 
 use cofd_util::{AllVariants, NameKey, VariantName};
 use enum_dispatch::enum_dispatch;
@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, VariantArray};
 
 use self::ability::Ability;
+use crate::character::{
+	Wound,
+	health::{FuelHealing, natural_healing_time},
+};
 
 pub mod ability;
 pub mod merits;
@@ -142,6 +146,21 @@ pub trait SplatTrait {
 		true
 	}
 
+	/// Whether `ability` is cheaper for this character, e.g. an in-clan Discipline.
+	fn is_affinity(&self, _ability: &Ability) -> bool {
+		false
+	}
+
+	/// Time a single box of `wound` takes to heal on its own, `None` if it doesn't.
+	fn healing_time(&self, wound: &Wound) -> Option<Duration> {
+		natural_healing_time(wound)
+	}
+
+	/// Fuel that can be spent to heal `wound`, if any.
+	fn fuel_healing(&self, _wound: &Wound) -> Option<FuelHealing> {
+		None
+	}
+
 	fn merits(&self) -> Vec<Merit>;
 }
 
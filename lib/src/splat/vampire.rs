@@ -1,12 +1,24 @@
-use cofd_schema::traits::DerivedTrait;
+use std::time::Duration;
+
+use cofd_schema::{
+	prerequisites::{
+		IntegrityRange, Prerequisite, PrerequisiteKey, Prerequisites, RatedPrerequisiteKey,
+	},
+	template::{SupernaturalTolerance, Template},
+	traits::{DerivedTrait, Trait as SchemaTrait},
+};
 use cofd_util::{AllVariants, VariantName};
 use serde::{Deserialize, Serialize};
 
-use super::{ability::Ability, Merit, Splat, SplatTrait, XSplat, YSplat, ZSplat};
+use super::{Merit, SplatTrait, XSplat, YSplat, ZSplat, ability::Ability};
 use crate::{
-	character::modifier::{Modifier, ModifierOp},
+	character::{
+		Wound,
+		health::FuelHealing,
+		modifier::{Modifier, ModifierOp},
+	},
 	dice_pool::DicePool,
-	prelude::{Attribute, Attributes, Skills, Trait},
+	prelude::{Attribute, Skill, Trait},
 };
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -80,7 +92,6 @@ impl SplatTrait for Vampire {
 		self.bloodline.clone().map(Into::into)
 	}
 
-
 	fn xsplats(&self) -> Vec<XSplat> {
 		Clan::all().into_iter().map(Into::into).collect()
 	}
@@ -127,6 +138,32 @@ impl SplatTrait for Vampire {
 	fn merits(&self) -> Vec<Merit> {
 		VampireMerit::all().map(Into::into).to_vec()
 	}
+
+	fn is_affinity(&self, ability: &Ability) -> bool {
+		let Ability::Discipline(discipline) = ability else {
+			return false;
+		};
+
+		self.clan.get_disciplines().contains(discipline)
+			|| matches!(
+				&self.bloodline,
+				Some(Bloodline::Custom(_, Some(disciplines))) if disciplines.contains(discipline)
+			)
+	}
+
+	// Vampires don't heal naturally, they mend with Vitae.
+	fn healing_time(&self, _wound: &Wound) -> Option<Duration> {
+		None
+	}
+
+	fn fuel_healing(&self, wound: &Wound) -> Option<FuelHealing> {
+		match wound {
+			Wound::None => None,
+			Wound::Bashing => Some(FuelHealing { fuel: 1, boxes: 2 }),
+			Wound::Lethal => Some(FuelHealing { fuel: 1, boxes: 1 }),
+			Wound::Aggravated => Some(FuelHealing { fuel: 5, boxes: 1 }),
+		}
+	}
 }
 
 impl Default for Vampire {
@@ -354,118 +391,94 @@ pub enum VampireMerit {
 }
 
 impl VampireMerit {
-	pub fn is_available(
-		&self,
-		character: &crate::prelude::Character,
-		attributes: &Attributes,
-		skills: &Skills,
-	) -> bool {
-		matches!(character.splat, Splat::Vampire(..))
-			&& match self {
-				// VampireMerit::Atrocious => todo!(), // Not Enticing or Cutthroat
-				VampireMerit::Bloodhound => attributes.wits >= 3,
-				VampireMerit::CallTheBeast => character.integrity < 5,
-				VampireMerit::ClawsOfTheUnholy => {
-					*character
-						.abilities
-						.get(&Discipline::Protean.into())
-						.unwrap_or(&0) >= 4
-				}
-				// VampireMerit::Cutthroat => todo!(), // Not Enticing or Atrocious
-				VampireMerit::DreamVisions => {
-					matches!(
-						character.splat,
-						Splat::Vampire(Vampire {
-							clan: Clan::Mekhet,
-							..
-						})
-					)
-				}
-				// VampireMerit::Enticing => todo!(), // Not Cutthroat or Atrocious
-				// VampireMerit::FeedingGrounds(_) => todo!(),
-				// VampireMerit::HeartOfStone => todo!(), // Feeding Grounds
-				// VampireMerit::HoneyTrap => todo!(), // Not a Revenant
-				// VampireMerit::KindredStatus(_) => todo!(),
-				VampireMerit::KissOfTheSuccubus => {
-					matches!(
-						character.splat,
-						Splat::Vampire(Vampire {
-							clan: Clan::Daeva,
-							..
-						})
-					)
-				}
-				// VampireMerit::Lineage(_) => todo!(), Clan Status
-				VampireMerit::LingeringDreams => {
-					matches!(
-						character.splat,
-						Splat::Vampire(Vampire {
-							clan: Clan::Mekhet,
-							..
-						})
-					)
-				} // Dream Visions
-				VampireMerit::PackAlpha => {
-					matches!(
-						character.splat,
-						Splat::Vampire(Vampire {
-							clan: Clan::Gangrel,
-							..
-						})
-					)
-				}
-				VampireMerit::ReceptiveMind => {
-					character.power >= 6
-						&& *character
-							.abilities
-							.get(&Discipline::Auspex.into())
-							.unwrap_or(&0) >= 4
-				}
-				VampireMerit::RevenantImpostor => {
-					attributes.manipulation >= 3 && skills.subterfuge >= 2
-				}
-				VampireMerit::SwarmForm => {
-					*character
-						.abilities
-						.get(&Discipline::Protean.into())
-						.unwrap_or(&0) >= 4
-				}
-				VampireMerit::UnsettlingGaze => {
-					matches!(
-						character.splat,
-						Splat::Vampire(Vampire {
-							clan: Clan::Nosferatu,
-							..
-						})
-					)
-				}
-
-				// VampireMerit::CacophonySavvy => todo!(), // City Status
-				VampireMerit::Courtoisie => {
-					attributes.composure >= 3 && skills.socialize >= 2 && skills.weaponry >= 2
-				} // Invictus Status
-				VampireMerit::Crusade => {
-					attributes.resolve >= 3 && skills.occult >= 2 && skills.weaponry >= 2
-				} // Theban Sorcery 2 or Sorc Eunuch
-				// VampireMerit::DynastyMembership => todo!(), // Clan Status
-				VampireMerit::KindredDueling => attributes.composure >= 3 && skills.weaponry >= 2,
-				VampireMerit::MobilizeOutrage => {
-					character.max_willpower() >= 5 && skills.brawl >= 2
-				} // Carthian Status
-				VampireMerit::RidingTheWave => attributes.composure >= 3 && attributes.resolve >= 3,
-				VampireMerit::RitesOfTheImpaled => {
-					attributes.resolve >= 3 && attributes.stamina >= 3 && skills.weaponry >= 2
-				} // Sworn
-				VampireMerit::TempleGuardian => {
-					skills.athletics >= 2 && skills.brawl >= 2 && skills.weaponry >= 2
-				} // Crone Status
-				// VampireMerit::IndependentStudy => todo!(),
-				// VampireMerit::SecretSocietyJunkie => todo!(),
-				// VampireMerit::Sworn(_) => todo!(),
-				// VampireMerit::TwilightJudge => todo!(),
-				// VampireMerit::NestGuardian => todo!(),
-				_ => true,
+	/// What a character needs to take the merit, starting with being a vampire.
+	pub fn prerequisites(&self) -> Prerequisites {
+		let rated = |trait_: SchemaTrait, rating| Prerequisite::rated(trait_, rating);
+		let discipline = |discipline: &str, rating| {
+			Prerequisite::rated(RatedPrerequisiteKey::Ability(discipline.to_owned()), rating)
+		};
+		let clan = |clan: &str| Prerequisite::from(PrerequisiteKey::Splat(clan.to_owned()));
+
+		let prerequisites = match self {
+			// VampireMerit::Atrocious => todo!(), // Not Enticing or Cutthroat
+			VampireMerit::Bloodhound => vec![rated(Attribute::Wits.into(), 3)],
+			VampireMerit::CallTheBeast => {
+				vec![PrerequisiteKey::Integrity(IntegrityRange::AtMost(4)).into()]
+			}
+			VampireMerit::ClawsOfTheUnholy | VampireMerit::SwarmForm => {
+				vec![discipline("Protean", 4)]
 			}
+			// VampireMerit::Cutthroat => todo!(), // Not Enticing or Atrocious
+			VampireMerit::DreamVisions => vec![clan("Mekhet")],
+			// VampireMerit::Enticing => todo!(), // Not Cutthroat or Atrocious
+			// VampireMerit::FeedingGrounds(_) => todo!(),
+			// VampireMerit::HeartOfStone => todo!(), // Feeding Grounds
+			// VampireMerit::HoneyTrap => todo!(), // Not a Revenant
+			// VampireMerit::KindredStatus(_) => todo!(),
+			VampireMerit::KissOfTheSuccubus => vec![clan("Daeva")],
+			// VampireMerit::Lineage(_) => todo!(), Clan Status
+			VampireMerit::LingeringDreams => vec![clan("Mekhet")], // Dream Visions
+			VampireMerit::PackAlpha => vec![clan("Gangrel")],
+			VampireMerit::ReceptiveMind => vec![
+				rated(SupernaturalTolerance::BloodPotency.into(), 6),
+				discipline("Auspex", 4),
+			],
+			VampireMerit::RevenantImpostor => vec![
+				rated(Attribute::Manipulation.into(), 3),
+				rated(Skill::Subterfuge.into(), 2),
+			],
+			VampireMerit::UnsettlingGaze => vec![clan("Nosferatu")],
+
+			// VampireMerit::CacophonySavvy => todo!(), // City Status
+			// Invictus Status
+			VampireMerit::Courtoisie => vec![
+				rated(Attribute::Composure.into(), 3),
+				rated(Skill::Socialize.into(), 2),
+				rated(Skill::Weaponry.into(), 2),
+			],
+			// Theban Sorcery 2 or Sorc Eunuch
+			VampireMerit::Crusade => vec![
+				rated(Attribute::Resolve.into(), 3),
+				rated(Skill::Occult.into(), 2),
+				rated(Skill::Weaponry.into(), 2),
+			],
+			// VampireMerit::DynastyMembership => todo!(), // Clan Status
+			VampireMerit::KindredDueling => vec![
+				rated(Attribute::Composure.into(), 3),
+				rated(Skill::Weaponry.into(), 2),
+			],
+			// Carthian Status
+			VampireMerit::MobilizeOutrage => vec![
+				rated(DerivedTrait::Willpower.into(), 5),
+				rated(Skill::Brawl.into(), 2),
+			],
+			VampireMerit::RidingTheWave => vec![
+				rated(Attribute::Composure.into(), 3),
+				rated(Attribute::Resolve.into(), 3),
+			],
+			// Sworn
+			VampireMerit::RitesOfTheImpaled => vec![
+				rated(Attribute::Resolve.into(), 3),
+				rated(Attribute::Stamina.into(), 3),
+				rated(Skill::Weaponry.into(), 2),
+			],
+			// Crone Status
+			VampireMerit::TempleGuardian => vec![
+				rated(Skill::Athletics.into(), 2),
+				rated(Skill::Brawl.into(), 2),
+				rated(Skill::Weaponry.into(), 2),
+			],
+			// VampireMerit::IndependentStudy => todo!(),
+			// VampireMerit::SecretSocietyJunkie => todo!(),
+			// VampireMerit::Sworn(_) => todo!(),
+			// VampireMerit::TwilightJudge => todo!(),
+			// VampireMerit::NestGuardian => todo!(),
+			_ => vec![],
+		};
+
+		std::iter::once(PrerequisiteKey::Template(Template::Vampire).into())
+			.chain(prerequisites)
+			.collect()
 	}
 
 	pub fn get_modifiers(&self, value: u16) -> Vec<Modifier> {
@@ -1,12 +1,20 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
-use cofd_schema::traits::DerivedTrait;
+use cofd_schema::{
+	prerequisites::{Prerequisite, PrerequisiteKey, Prerequisites},
+	template::{SupernaturalTolerance, Template},
+	traits::{DerivedTrait, Trait as SchemaTrait},
+};
 use cofd_util::VariantName;
 use serde::{Deserialize, Serialize};
 
-use super::{ability::Ability, Merit, NameKey, Splat, SplatTrait, XSplat, YSplat, ZSplat};
+use super::{Merit, NameKey, Splat, SplatTrait, XSplat, YSplat, ZSplat, ability::Ability};
 use crate::{
-	character::modifier::{Modifier, ModifierOp, ModifierTarget},
+	character::{
+		Wound,
+		health::{BASHING_HEALING_TIME, TURN, natural_healing_time},
+		modifier::{Modifier, ModifierOp, ModifierTarget, RollContext},
+	},
 	dice_pool::DicePool,
 	prelude::*,
 };
@@ -161,6 +169,15 @@ impl SplatTrait for Werewolf {
 	fn merits(&self) -> Vec<Merit> {
 		WerewolfMerit::all().map(Into::into).to_vec()
 	}
+
+	// Regeneration: a bashing box every turn, a lethal box every 15 minutes.
+	fn healing_time(&self, wound: &Wound) -> Option<Duration> {
+		match wound {
+			Wound::Bashing => Some(TURN),
+			Wound::Lethal => Some(BASHING_HEALING_TIME),
+			_ => natural_healing_time(wound),
+		}
+	}
 }
 
 #[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -761,17 +778,19 @@ pub enum WerewolfMerit {
 }
 
 impl WerewolfMerit {
-	pub fn is_available(&self, character: &crate::prelude::Character) -> bool {
-		if matches!(character.splat, Splat::Werewolf(..)) {
-			match self {
-				Self::InstinctiveDefense => {
-					character.power >= 2 && character.skills().athletics >= 2
-				}
-				_ => true,
-			}
-		} else {
-			false
-		}
+	/// What a character needs to take the merit, starting with being a werewolf.
+	pub fn prerequisites(&self) -> Prerequisites {
+		let prerequisites = match self {
+			Self::InstinctiveDefense => vec![
+				Prerequisite::rated(SchemaTrait::from(SupernaturalTolerance::PrimalUrge), 2),
+				Prerequisite::rated(SchemaTrait::from(Skill::Athletics), 2),
+			],
+			_ => vec![],
+		};
+
+		std::iter::once(PrerequisiteKey::Template(Template::Werewolf).into())
+			.chain(prerequisites)
+			.collect()
 	}
 
 	pub fn get_modifiers(&self, value: u16) -> Vec<Modifier> {
@@ -816,7 +835,8 @@ pub fn get_form_trait(character: &Character, form: &Form, target: &ModifierTarge
 	let value = match target {
 		ModifierTarget::BaseAttribute(_)
 		| ModifierTarget::BaseSkill(_)
-		| ModifierTarget::Skill(_) => unreachable!(),
+		| ModifierTarget::Skill(_)
+		| ModifierTarget::Roll => unreachable!(),
 		ModifierTarget::Attribute(attr) => *character.attributes().get(attr) as i16,
 		ModifierTarget::Trait(trait_) => character.get_trait(trait_) as i16,
 	};
@@ -875,7 +895,10 @@ fn form_modifier(
 	target: &(impl Into<ModifierTarget> + Clone),
 ) -> i16 {
 	character
-		.get_conditional_modifier((*target).clone(), form.clone())
+		.get_conditional_modifier(
+			(*target).clone(),
+			&RollContext::default().with_form(form.clone()),
+		)
 		.unwrap_or(0)
 }
 
@@ -885,6 +908,9 @@ fn form_pool(
 	target: &(impl Into<ModifierTarget> + Clone),
 ) -> DicePool {
 	character
-		.get_conditional_pool((*target).clone(), form.clone())
+		.get_conditional_pool(
+			(*target).clone(),
+			&RollContext::default().with_form(form.clone()),
+		)
 		.unwrap_or(DicePool::Mod(0))
 }
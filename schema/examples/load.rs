@@ -1,11 +1,20 @@
-use cofd_schema::book::Book;
-use std::fs::File;
+use cofd_schema::library::Library;
+use std::path::Path;
 
 fn main() {
 	let path = std::env::args().nth(1).expect("Usage: load <path>");
+	let path = Path::new(&path);
 
-	let book: Book =
-		ron::de::from_reader(File::open(path).expect("")).expect("metadata parse error");
+	if path.is_dir() {
+		let library = Library::load_dir(path).expect("metadata parse error");
 
-	println!("{:#?}", book);
+		for conflict in library.conflicts() {
+			println!("{conflict}");
+		}
+		println!("{} books loaded", library.books().count());
+	} else {
+		let book = Library::load_book(path).expect("metadata parse error");
+
+		println!("{:#?}", book);
+	}
 }
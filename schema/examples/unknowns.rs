@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use cofd_schema::library::Library;
+
+/// Lists every prerequisite the parser didn't understand in a book or a directory of books,
+/// and every merit prerequisite naming a merit none of the books define.
+fn main() {
+	let path = std::env::args().nth(1).expect("Usage: unknowns <path>");
+	let path = Path::new(&path);
+
+	let library = if path.is_dir() {
+		Library::load_dir(path).expect("metadata parse error")
+	} else {
+		let mut library = Library::new();
+		library.add_book(Library::load_book(path).expect("metadata parse error"));
+		library
+	};
+
+	let mut count = 0;
+	for book in library.books() {
+		for unknown in book.unknown_prerequisites() {
+			println!("{}: {unknown}", book.info.name);
+			count += 1;
+		}
+	}
+	println!("{count} unknown prerequisites");
+
+	let undefined = library.undefined_merits();
+	for merit in &undefined {
+		println!("{merit}");
+	}
+	println!("{} undefined merits", undefined.len());
+}
@@ -11,10 +11,11 @@ use crate::{
 		merit::Merit,
 		spell::Spell,
 	},
+	prerequisites::Prerequisites,
 };
 
 #[derive(
-	Default, Debug, Clone, Copy, Serialize, Deserialize, EnumString, Display, PartialEq, Eq,
+	Default, Debug, Clone, Copy, Serialize, Deserialize, EnumString, Display, PartialEq, Eq, Hash,
 )]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[strum(ascii_case_insensitive)]
@@ -58,9 +59,10 @@ pub enum BookId {
 pub struct BookInfo {
 	pub name: String,
 	pub id: BookId,
-	#[serde(with = "hex")]
+	#[serde(with = "hex", default)]
 	#[cfg_attr(feature = "json_schema", schemars(with = "String"))]
 	pub hash: u64,
+	#[serde(default)]
 	pub publication_date: chrono::NaiveDate,
 }
 
@@ -109,11 +111,72 @@ impl From<BookInfo> for Book {
 	}
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, derive_more::Display)]
+impl Book {
+	/// Parses merit prerequisites stored as text, see
+	/// [`Prerequisites::reparse`](crate::prerequisites::Prerequisites::reparse).
+	pub fn reparse_prerequisites(&mut self) {
+		for merit in &mut self.merits {
+			merit.inner.prerequisites.reparse();
+			for child in &mut merit.inner.children {
+				child.prerequisites.reparse();
+			}
+		}
+	}
+
+	/// Every prerequisite of the book's items that couldn't be parsed, for data
+	/// maintainers to rewrite.
+	#[must_use]
+	pub fn unknown_prerequisites(&self) -> Vec<UnknownPrerequisite<'_>> {
+		let mut report = Vec::new();
+		for (item, reference, prereqs) in self.item_prerequisites() {
+			for prerequisite in prereqs.unknowns() {
+				report.push(UnknownPrerequisite {
+					item: item.clone(),
+					reference,
+					prerequisite,
+				});
+			}
+		}
+		report
+	}
+
+	/// The prerequisites of every merit and merit child, with the item's name.
+	pub(crate) fn item_prerequisites(
+		&self,
+	) -> impl Iterator<Item = (String, &BookReference, &Prerequisites)> {
+		self.merits.iter().flat_map(|merit| {
+			std::iter::once((
+				merit.name.clone(),
+				&merit.reference,
+				&merit.inner.prerequisites,
+			))
+			.chain(merit.inner.children.iter().map(|child| {
+				(
+					format!("{} ({})", merit.name, child.name),
+					&merit.reference,
+					&child.prerequisites,
+				)
+			}))
+		})
+	}
+}
+
+/// A prerequisite of a book's item that the parser didn't understand, or that refers to
+/// a merit no loaded book defines.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display("{reference}: {item}: {prerequisite}")]
+pub struct UnknownPrerequisite<'a> {
+	pub item: String,
+	pub reference: &'a BookReference,
+	pub prerequisite: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, derive_more::Display)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[display("{_0} pg.{_1}")]
 pub struct BookReference(pub BookId, pub usize);
 
+/// The default reference, page 0, marks an item whose page isn't known.
 impl Default for BookReference {
 	fn default() -> Self {
 		Self(BookId::CofD, Default::default())
@@ -0,0 +1,235 @@
+use std::{
+	fmt::{self, Display},
+	ops::{Add, Sub},
+};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::traits::{Trait, attribute::Attribute, skill::Skill};
+
+mod parse;
+
+pub use parse::{PoolParseError, PoolParseErrorKind};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[serde(tag = "kind", content = "value")]
+pub enum DicePool {
+	Mod(i8),
+
+	Trait(Trait),
+
+	Min(Box<DicePool>, Box<DicePool>),
+	Max(Box<DicePool>, Box<DicePool>),
+
+	Add(Vec<DicePool>),
+	Sub(Box<DicePool>, Box<DicePool>),
+	Vs(Box<DicePool>, Box<DicePool>),
+
+	Key(String),
+}
+
+impl DicePool {
+	// pub fn value(&self, character: &Character) -> i16 {
+	// 	match self {
+	// 		Self::Mod(val) => *val,
+	// 		Self::Attribute(attr) => *character.attributes().get(attr) as i16,
+	// 		Self::Skill(skill) => character.skills().get(*skill) as i16,
+	// 		Self::Trait(trait_) => character.get_trait(trait_) as i16,
+	// 		Self::Add(p1, p2) => p1.value(character) + p2.value(character),
+	// 		Self::Sub(p1, p2) => p1.value(character) - p2.value(character),
+	// 		Self::Max(p1, p2) => max(p1.value(character), p2.value(character)),
+	// 		Self::Min(p1, p2) => min(p1.value(character), p2.value(character)),
+	// 	}
+	// }
+
+	pub fn min(p1: impl Into<DicePool>, p2: impl Into<DicePool>) -> DicePool {
+		DicePool::Min(Box::new(p1.into()), Box::new(p2.into()))
+	}
+
+	pub fn max(p1: impl Into<DicePool>, p2: impl Into<DicePool>) -> DicePool {
+		DicePool::Max(Box::new(p1.into()), Box::new(p2.into()))
+	}
+
+	pub fn vs(p1: impl Into<DicePool>, p2: impl Into<DicePool>) -> DicePool {
+		DicePool::Vs(Box::new(p1.into()), Box::new(p2.into()))
+	}
+
+	fn is_compound(&self) -> bool {
+		matches!(
+			self,
+			DicePool::Add(_) | DicePool::Sub(..) | DicePool::Vs(..)
+		)
+	}
+
+	/// Splits a contested (`X vs Y`) pool into the actor's and the opponent's pools.
+	#[must_use]
+	pub fn contested(&self) -> Option<(&DicePool, &DicePool)> {
+		match self {
+			DicePool::Vs(actor, opponent) => Some((actor, opponent)),
+			_ => None,
+		}
+	}
+
+	/// Splits a resisted (`X - Y`) pool into the actor's pool and the opponent's resistance.
+	///
+	/// Only traits and keys, or combinations of them, are resisted, so a constant penalty
+	/// like `Strength + Brawl - 2` stays on the actor.
+	#[must_use]
+	pub fn resisted(&self) -> Option<(&DicePool, &DicePool)> {
+		match self {
+			DicePool::Sub(pool, resistance) if resistance.is_resistance() => {
+				Some((pool, resistance))
+			}
+			_ => None,
+		}
+	}
+
+	fn is_resistance(&self) -> bool {
+		match self {
+			DicePool::Trait(_) | DicePool::Key(_) => true,
+			DicePool::Add(vec) => vec.iter().all(DicePool::is_resistance),
+			DicePool::Min(p1, p2) | DicePool::Max(p1, p2) => {
+				p1.is_resistance() && p2.is_resistance()
+			}
+			DicePool::Mod(_) | DicePool::Sub(..) | DicePool::Vs(..) => false,
+		}
+	}
+}
+
+impl Display for DicePool {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			DicePool::Mod(val) => write!(f, "{val}"),
+			DicePool::Trait(trait_) => write!(f, "{trait_}"),
+			DicePool::Min(p1, p2) => write!(
+				f,
+				"Lower of {} and {}",
+				Parens(p1, p1.is_compound()),
+				Parens(p2, p2.is_compound())
+			),
+			DicePool::Max(p1, p2) => write!(
+				f,
+				"Higher of {} and {}",
+				Parens(p1, p1.is_compound()),
+				Parens(p2, p2.is_compound())
+			),
+			DicePool::Add(vec) => write!(
+				f,
+				"{}",
+				vec.iter().enumerate().format_with(" + ", |(i, pool), f| {
+					let parens = match pool {
+						DicePool::Add(_) | DicePool::Vs(..) => true,
+						DicePool::Sub(..) => i > 0,
+						_ => false,
+					};
+					f(&Parens(pool, parens))
+				})
+			),
+			DicePool::Sub(p1, p2) => write!(
+				f,
+				"{} - {}",
+				Parens(p1, matches!(**p1, DicePool::Vs(..))),
+				Parens(p2, p2.is_compound())
+			),
+			DicePool::Vs(p1, p2) => {
+				write!(
+					f,
+					"{p1} vs {}",
+					Parens(p2, matches!(**p2, DicePool::Vs(..)))
+				)
+			}
+			DicePool::Key(key) if parse::needs_quotes(key) => {
+				write!(f, "\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+			}
+			DicePool::Key(key) => write!(f, "{key}"),
+		}
+	}
+}
+
+/// Parenthesizes a nested pool where its text would otherwise parse differently.
+struct Parens<'a>(&'a DicePool, bool);
+
+impl Display for Parens<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.1 {
+			write!(f, "({})", self.0)
+		} else {
+			write!(f, "{}", self.0)
+		}
+	}
+}
+
+impl Default for DicePool {
+	fn default() -> Self {
+		Self::Mod(0)
+	}
+}
+
+impl<T: Into<DicePool>> Add<T> for DicePool {
+	type Output = DicePool;
+
+	fn add(self, rhs: T) -> Self::Output {
+		let rhs = rhs.into();
+		let mut vec = match self {
+			DicePool::Add(v) => v,
+			_ => vec![self],
+		};
+
+		match rhs {
+			DicePool::Add(v) => vec.extend(v),
+			_ => vec.push(rhs),
+		}
+
+		Self::Add(vec)
+	}
+}
+
+impl<T: Into<DicePool>> Sub<T> for DicePool {
+	type Output = DicePool;
+
+	fn sub(self, rhs: T) -> Self::Output {
+		Self::Sub(Box::new(self), Box::new(rhs.into()))
+	}
+}
+
+impl From<i8> for DicePool {
+	fn from(val: i8) -> Self {
+		Self::Mod(val)
+	}
+}
+
+impl<T: Into<Trait>> From<T> for DicePool {
+	fn from(value: T) -> Self {
+		DicePool::Trait(value.into())
+	}
+}
+
+impl<T: Into<DicePool>> Add<T> for Attribute {
+	type Output = DicePool;
+
+	fn add(self, rhs: T) -> Self::Output {
+		DicePool::Trait(self.into()) + rhs.into()
+	}
+}
+
+impl<T: Into<DicePool>> Add<T> for Skill {
+	type Output = DicePool;
+
+	fn add(self, rhs: T) -> Self::Output {
+		DicePool::Trait(self.into()) + rhs.into()
+	}
+}
+
+impl From<Vec<DicePool>> for DicePool {
+	fn from(mut value: Vec<DicePool>) -> Self {
+		if value.len() > 1 {
+			Self::Add(value)
+		} else if let Some(value) = value.pop() {
+			value
+		} else {
+			Self::Mod(0)
+		}
+	}
+}
@@ -0,0 +1,441 @@
+use std::{ops::Range, str::FromStr};
+
+use thiserror::Error;
+
+use super::DicePool;
+use crate::traits::Trait;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PoolParseErrorKind {
+	#[error("expected a trait, number or expression")]
+	ExpectedTerm,
+	#[error("expected \"{_0}\"")]
+	Expected(&'static str),
+	#[error("unexpected \"{_0}\"")]
+	Unexpected(String),
+	#[error("unclosed parenthesis")]
+	UnclosedParen,
+	#[error("unclosed quote")]
+	UnclosedQuote,
+	#[error("number out of range")]
+	NumberOutOfRange,
+}
+
+/**
+ * Dice pool parsing error, with the byte span of the offending input
+ */
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{kind} at {}..{}", span.start, span.end)]
+pub struct PoolParseError {
+	pub kind: PoolParseErrorKind,
+	pub span: Range<usize>,
+}
+
+impl PoolParseError {
+	fn new(kind: PoolParseErrorKind, span: Range<usize>) -> Self {
+		Self { kind, span }
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+	LParen,
+	RParen,
+	Plus,
+	Minus,
+	Comma,
+	Number(&'a str),
+	Word(&'a str),
+	/// A quoted name, still escaped, and whether its closing quote was found.
+	Quoted(&'a str, bool),
+}
+
+fn is_minus(c: char) -> bool {
+	matches!(c, '-' | '−' | '–' | '—')
+}
+
+fn is_word_char(c: char) -> bool {
+	!(c.is_whitespace() || matches!(c, '(' | ')' | '+' | ',' | '"') || is_minus(c))
+}
+
+fn tokenize(str: &str) -> Vec<(Token<'_>, Range<usize>)> {
+	let mut tokens = Vec::new();
+	let mut chars = str.char_indices().peekable();
+
+	while let Some((start, c)) = chars.next() {
+		let token = match c {
+			c if c.is_whitespace() => continue,
+			'(' => Token::LParen,
+			')' => Token::RParen,
+			'+' => Token::Plus,
+			',' => Token::Comma,
+			c if is_minus(c) => Token::Minus,
+			'"' => {
+				let mut escaped = false;
+				let end = chars.find(|&(_, c)| {
+					let close = c == '"' && !escaped;
+					escaped = c == '\\' && !escaped;
+					close
+				});
+
+				let token = match end {
+					Some((end, _)) => (Token::Quoted(&str[start + 1..end], true), start..end + 1),
+					None => (Token::Quoted(&str[start + 1..], false), start..str.len()),
+				};
+				tokens.push(token);
+				continue;
+			}
+			_ => {
+				let mut end = start + c.len_utf8();
+				while let Some(&(i, c)) = chars.peek() {
+					// Hyphenated names such as "Wolf-Blooded" stay a single word.
+					let hyphen = c == '-'
+						&& str[..i]
+							.chars()
+							.next_back()
+							.is_some_and(char::is_alphabetic)
+						&& str[i + 1..].chars().next().is_some_and(char::is_alphabetic);
+
+					if is_word_char(c) || hyphen {
+						end = i + c.len_utf8();
+						chars.next();
+					} else {
+						break;
+					}
+				}
+
+				let word = &str[start..end];
+				let word_end = start + word.trim_end_matches('.').len();
+				let word = &str[start..word_end.max(start + 1)];
+
+				let token = if word.chars().all(|c| c.is_ascii_digit()) {
+					Token::Number(word)
+				} else {
+					Token::Word(word)
+				};
+				tokens.push((token, start..start + word.len()));
+				continue;
+			}
+		};
+
+		tokens.push((token, start..start + c.len_utf8()));
+	}
+
+	tokens
+}
+
+fn keyword(token: Option<&(Token<'_>, Range<usize>)>, keywords: &[&str]) -> bool {
+	matches!(token, Some((Token::Word(word), _)) if keywords.iter().any(|k| word.eq_ignore_ascii_case(k)))
+}
+
+const VS: &[&str] = &["vs", "vs.", "versus"];
+
+struct Parser<'a> {
+	str: &'a str,
+	tokens: Vec<(Token<'a>, Range<usize>)>,
+	pos: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn peek(&self) -> Option<&(Token<'a>, Range<usize>)> {
+		self.tokens.get(self.pos)
+	}
+
+	fn peek_nth(&self, n: usize) -> Option<&(Token<'a>, Range<usize>)> {
+		self.tokens.get(self.pos + n)
+	}
+
+	fn next(&mut self) -> Option<(Token<'a>, Range<usize>)> {
+		let token = self.tokens.get(self.pos).cloned();
+		self.pos += 1;
+		token
+	}
+
+	fn eof_span(&self) -> Range<usize> {
+		self.str.len()..self.str.len()
+	}
+
+	fn span(&self) -> Range<usize> {
+		self.peek()
+			.map_or_else(|| self.eof_span(), |(_, span)| span.clone())
+	}
+
+	fn expect_keyword(
+		&mut self,
+		keywords: &[&str],
+		name: &'static str,
+	) -> Result<(), PoolParseError> {
+		if keyword(self.peek(), keywords) {
+			self.pos += 1;
+			Ok(())
+		} else {
+			Err(PoolParseError::new(
+				PoolParseErrorKind::Expected(name),
+				self.span(),
+			))
+		}
+	}
+
+	fn expr(&mut self) -> Result<DicePool, PoolParseError> {
+		let mut pool = self.additive()?;
+
+		while keyword(self.peek(), VS) {
+			self.pos += 1;
+			pool = DicePool::Vs(Box::new(pool), Box::new(self.additive()?));
+		}
+
+		Ok(pool)
+	}
+
+	fn additive(&mut self) -> Result<DicePool, PoolParseError> {
+		let mut pool = self.term(false)?;
+		// Whether `pool` is an `Add` built by this loop, rather than a parenthesized one.
+		let mut adding = false;
+
+		loop {
+			let sub = match self.peek() {
+				Some((Token::Plus | Token::Comma, _)) => false,
+				Some((Token::Minus, _)) => true,
+				_ => break,
+			};
+			self.pos += 1;
+
+			let term = self.term(false)?;
+			pool = match pool {
+				DicePool::Add(mut vec) if adding && !sub => {
+					vec.push(term);
+					DicePool::Add(vec)
+				}
+				pool if sub => DicePool::Sub(Box::new(pool), Box::new(term)),
+				pool => DicePool::Add(vec![pool, term]),
+			};
+			adding = !sub;
+		}
+
+		Ok(pool)
+	}
+
+	/// A single operand. `in_comparison` stops names at "and"/"or".
+	fn term(&mut self, in_comparison: bool) -> Result<DicePool, PoolParseError> {
+		if keyword(self.peek(), &["the"])
+			&& keyword(self.peek_nth(1), &["lower", "higher"])
+			&& keyword(self.peek_nth(2), &["of"])
+		{
+			self.pos += 1;
+		}
+
+		if keyword(self.peek(), &["lower", "higher"]) && keyword(self.peek_nth(1), &["of"]) {
+			let min = keyword(self.peek(), &["lower"]);
+			self.pos += 2;
+
+			let p1 = self.term(true)?;
+			self.expect_keyword(&["and", "or"], "and")?;
+			let p2 = self.term(in_comparison)?;
+
+			return Ok(if min {
+				DicePool::min(p1, p2)
+			} else {
+				DicePool::max(p1, p2)
+			});
+		}
+
+		match self.next() {
+			Some((Token::LParen, start)) => {
+				let pool = self.expr()?;
+				match self.next() {
+					Some((Token::RParen, _)) => Ok(pool),
+					Some((_, span)) => {
+						Err(PoolParseError::new(PoolParseErrorKind::Expected(")"), span))
+					}
+					None => Err(PoolParseError::new(
+						PoolParseErrorKind::UnclosedParen,
+						start.start..self.str.len(),
+					)),
+				}
+			}
+			Some((sign @ (Token::Plus | Token::Minus), span)) => match self.next() {
+				Some((Token::Number(num), num_span)) => {
+					let span = span.start..num_span.end;
+					let sign = if sign == Token::Minus { "-" } else { "" };
+					i8::from_str(&format!("{sign}{num}"))
+						.map(DicePool::Mod)
+						.map_err(|_| {
+							PoolParseError::new(PoolParseErrorKind::NumberOutOfRange, span)
+						})
+				}
+				_ => Err(PoolParseError::new(PoolParseErrorKind::ExpectedTerm, span)),
+			},
+			Some((Token::Number(num), span)) => i8::from_str(num)
+				.map(DicePool::Mod)
+				.map_err(|_| PoolParseError::new(PoolParseErrorKind::NumberOutOfRange, span)),
+			Some((Token::Word(_), span)) => {
+				if VS
+					.iter()
+					.any(|k| self.str[span.clone()].eq_ignore_ascii_case(k))
+				{
+					return Err(PoolParseError::new(PoolParseErrorKind::ExpectedTerm, span));
+				}
+
+				let mut end = span.end;
+				while let Some((Token::Word(_), next)) = self.peek() {
+					if keyword(self.peek(), VS)
+						|| (in_comparison && keyword(self.peek(), &["and", "or"]))
+					{
+						break;
+					}
+					end = next.end;
+					self.pos += 1;
+				}
+
+				Ok(name(&self.str[span.start..end]))
+			}
+			Some((Token::Quoted(text, true), _)) => Ok(DicePool::Key(unescape(text))),
+			Some((Token::Quoted(_, false), span)) => {
+				Err(PoolParseError::new(PoolParseErrorKind::UnclosedQuote, span))
+			}
+			Some((Token::RParen, span)) => Err(PoolParseError::new(
+				PoolParseErrorKind::Unexpected(")".to_owned()),
+				span,
+			)),
+			Some((_, span)) => Err(PoolParseError::new(PoolParseErrorKind::ExpectedTerm, span)),
+			None => Err(PoolParseError::new(
+				PoolParseErrorKind::ExpectedTerm,
+				self.eof_span(),
+			)),
+		}
+	}
+}
+
+fn unescape(text: &str) -> String {
+	let mut str = String::with_capacity(text.len());
+	let mut chars = text.chars();
+	while let Some(c) = chars.next() {
+		str.push(if c == '\\' {
+			chars.next().unwrap_or(c)
+		} else {
+			c
+		});
+	}
+	str
+}
+
+/// Whether a [`DicePool::Key`] has to be quoted to be read back as the same key.
+pub(super) fn needs_quotes(key: &str) -> bool {
+	key.split_whitespace()
+		.any(|word| ["and", "or"].iter().any(|k| word.eq_ignore_ascii_case(k)))
+		|| !matches!(DicePool::from_str(key), Ok(DicePool::Key(k)) if k == key)
+}
+
+fn name(name: &str) -> DicePool {
+	Trait::from_str(name)
+		.or_else(|_| Trait::from_str(&name.replace(' ', "")))
+		.map_or_else(|_| DicePool::Key(name.to_owned()), DicePool::Trait)
+}
+
+impl FromStr for DicePool {
+	type Err = PoolParseError;
+
+	fn from_str(str: &str) -> Result<Self, Self::Err> {
+		let mut parser = Parser {
+			str,
+			tokens: tokenize(str),
+			pos: 0,
+		};
+
+		let pool = parser.expr()?;
+
+		if let Some((_, span)) = parser.peek() {
+			Err(PoolParseError::new(
+				PoolParseErrorKind::Unexpected(str[span.clone()].to_owned()),
+				span.clone(),
+			))
+		} else {
+			Ok(pool)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::traits::{DerivedTrait, attribute::Attribute, skill::Skill};
+
+	#[test]
+	fn parse() {
+		assert_eq!(
+			DicePool::from_str("Dexterity + Athletics + Celerity − target's Defense").unwrap(),
+			(Attribute::Dexterity + Skill::Athletics + DicePool::Key("Celerity".to_owned()))
+				- DicePool::Key("target's Defense".to_owned())
+		);
+		assert_eq!(
+			DicePool::from_str("Resolve + Composure, lower of Wits or Dexterity").unwrap(),
+			Attribute::Resolve
+				+ Attribute::Composure
+				+ DicePool::min(Attribute::Wits, Attribute::Dexterity)
+		);
+		assert_eq!(
+			DicePool::from_str("Strength + Brawl + 2 vs Defense.").unwrap(),
+			DicePool::vs(
+				Attribute::Strength + Skill::Brawl + DicePool::Mod(2),
+				DerivedTrait::Defense
+			)
+		);
+		assert_eq!(
+			DicePool::from_str("Animal Ken - (Composure + -1)").unwrap(),
+			DicePool::from(Skill::AnimalKen) - (Attribute::Composure + DicePool::Mod(-1))
+		);
+	}
+
+	#[test]
+	fn round_trip() {
+		let pools = [
+			DicePool::min(Attribute::Wits, Attribute::Dexterity) + Skill::Athletics,
+			DicePool::max(
+				Attribute::Wits + Skill::Empathy,
+				DicePool::min(Attribute::Presence, DicePool::Mod(-2)),
+			),
+			Attribute::Presence
+				+ (DicePool::from(Skill::Intimidation)
+					- DicePool::Key("target's Resolve".to_owned())),
+			DicePool::Add(vec![
+				Attribute::Strength + Skill::Brawl,
+				DicePool::Key("Vigor".to_owned()),
+			]),
+			DicePool::vs(
+				Attribute::Manipulation + Skill::Persuasion,
+				DicePool::vs(Attribute::Resolve, Attribute::Composure),
+			),
+			Attribute::Wits + DicePool::Key("Level 2".to_owned()),
+			DicePool::min(
+				DicePool::Key("Wits or Luck".to_owned()),
+				DicePool::Key("Strength".to_owned()),
+			) - DicePool::Key("a \"quoted\" \\ name".to_owned()),
+		];
+
+		for pool in pools {
+			assert_eq!(DicePool::from_str(&pool.to_string()).unwrap(), pool);
+		}
+	}
+
+	#[test]
+	fn errors() {
+		let err = DicePool::from_str("Strength + (Brawl").unwrap_err();
+		assert_eq!(err.kind, PoolParseErrorKind::UnclosedParen);
+		assert_eq!(err.span, 11..17);
+
+		let err = DicePool::from_str("Strength + + Brawl").unwrap_err();
+		assert_eq!(err.kind, PoolParseErrorKind::ExpectedTerm);
+		assert_eq!(err.span, 11..12);
+
+		let err = DicePool::from_str("Lower of Wits Dexterity").unwrap_err();
+		assert_eq!(err.kind, PoolParseErrorKind::Expected("and"));
+		assert_eq!(err.span, 23..23);
+
+		let err = DicePool::from_str("Wits)").unwrap_err();
+		assert_eq!(err.span, 4..5);
+
+		let err = DicePool::from_str("Wits + \"Level 2").unwrap_err();
+		assert_eq!(err.kind, PoolParseErrorKind::UnclosedQuote);
+		assert_eq!(err.span, 7..15);
+	}
+}
@@ -39,6 +39,32 @@ impl Default for DotRange {
 	}
 }
 
+/// Where open ranges end, as no merit goes above five dots.
+const MAX_DOTS: u8 = 5;
+
+impl DotRange {
+	#[must_use]
+	pub fn contains(&self, rating: u8) -> bool {
+		match self {
+			Self::Num(num) => *num == rating,
+			Self::Set(set) => set.contains(&rating),
+			Self::Range(range) => range.contains(&rating),
+			Self::RangeFrom(range) => range.contains(&rating),
+		}
+	}
+
+	/// Every rating in the range, in order, with open ranges ending at five dots.
+	#[must_use]
+	pub fn valid_values(&self) -> Vec<u8> {
+		match self {
+			Self::Num(num) => vec![*num],
+			Self::Set(set) => set.iter().copied().sorted().collect(),
+			Self::Range(range) => range.clone().collect(),
+			Self::RangeFrom(range) => (range.start..=MAX_DOTS).collect(),
+		}
+	}
+}
+
 /**
  * Function to convert a string of '•' characters to a number
  * # Errors
@@ -95,3 +121,52 @@ impl FromStr for DotRange {
 		})
 	}
 }
+
+/// (De)serializes an optional [`DotRange`] as the bare range, so data can leave it out
+/// without wrapping it in `Some` when it's given.
+pub(crate) mod optional {
+	use serde::{Deserialize, Serialize};
+
+	use super::DotRange;
+
+	#[allow(clippy::ref_option)]
+	pub fn serialize<S>(v: &Option<DotRange>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		match v {
+			Some(range) => range.serialize(serializer),
+			None => serializer.serialize_none(),
+		}
+	}
+
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DotRange>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		DotRange::deserialize(deserializer).map(Some)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn valid_values() {
+		let range = DotRange::from_str("•• to ••••").unwrap();
+		assert!(range.contains(3));
+		assert!(!range.contains(1));
+		assert_eq!(range.valid_values(), vec![2, 3, 4]);
+
+		let set = DotRange::from_str("••• or •").unwrap();
+		assert!(!set.contains(2));
+		assert_eq!(set.valid_values(), vec![1, 3]);
+
+		assert_eq!(
+			DotRange::from_str("•••+").unwrap().valid_values(),
+			vec![3, 4, 5]
+		);
+		assert_eq!(DotRange::Num(2).valid_values(), vec![2]);
+	}
+}
@@ -2,6 +2,8 @@ use std::num::{ParseIntError, TryFromIntError};
 
 use thiserror::Error;
 
+use crate::dice_pool::PoolParseError;
+
 #[derive(Error, Debug)]
 pub enum ParseError {
 	#[error(transparent)]
@@ -10,6 +12,8 @@ pub enum ParseError {
 	IntError(#[from] ParseIntError),
 	#[error(transparent)]
 	TryFromIntError(#[from] TryFromIntError),
+	#[error(transparent)]
+	DicePoolError(#[from] PoolParseError),
 	#[error("The provided data was in a wrong format: {_0}")]
 	BadFormat(String),
 }
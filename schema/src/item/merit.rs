@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, Display, EnumString};
 
 use super::ActionFields;
-use crate::{prelude::DotRange, prerequisites::Prerequisites};
+use crate::{modifiers::ItemModifier, prelude::DotRange, prerequisites::Prerequisites};
 
 #[derive(
 	Debug, Clone, Copy, Serialize, Deserialize, EnumString, AsRefStr, PartialEq, Eq, Display,
@@ -35,13 +35,42 @@ pub struct MeritSubItem {
 	pub dot_rating: DotRange,
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub drawbacks: Vec<String>,
+	/// Mechanical effects applied once the merit's rating unlocks the sub-item.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub modifiers: Vec<ItemModifier>,
+}
+
+impl MeritSubItem {
+	/// Lowest rating of the merit that unlocks the sub-item, e.g. the dot a Fighting
+	/// Style's maneuver is bought at.
+	#[must_use]
+	pub fn min_rating(&self) -> u8 {
+		self.dot_rating
+			.valid_values()
+			.first()
+			.copied()
+			.unwrap_or_default()
+	}
+
+	/// Whether the sub-item is unlocked at the merit's rating.
+	#[must_use]
+	pub fn is_unlocked(&self, rating: u8) -> bool {
+		rating > 0 && rating >= self.min_rating()
+	}
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Merit {
-	pub dot_rating: DotRange,
+	/// The ratings the merit can be bought at, `None` if the data doesn't say.
+	#[serde(
+		default,
+		with = "crate::dot_range::optional",
+		skip_serializing_if = "Option::is_none"
+	)]
+	#[cfg_attr(feature = "json_schema", schemars(with = "Option<DotRange>"))]
+	pub dot_rating: Option<DotRange>,
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub prerequisites: Prerequisites,
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
+use strum::Display;
 
 use self::merit::Merit;
 use crate::{
 	book::{BookReference, MoonGift, OtherGift},
 	dice_pool::DicePool,
-	modifiers::SuggestedModifiers,
+	modifiers::{ItemModifier, SuggestedModifiers},
 };
 
 pub mod gift;
@@ -33,6 +34,51 @@ impl RollResults {
 			&& self.failure.is_empty()
 			&& self.dramatic_failure.is_empty()
 	}
+
+	#[must_use]
+	pub fn get(&self, outcome: RollOutcome) -> &[String] {
+		match outcome {
+			RollOutcome::ExceptionalSuccess => &self.exceptional_success,
+			RollOutcome::Success => &self.success,
+			RollOutcome::Failure => &self.failure,
+			RollOutcome::DramaticFailure => &self.dramatic_failure,
+		}
+	}
+
+	/// Effect text for an outcome. Exceptional successes and dramatic failures without
+	/// their own text fall back to the success and failure text respectively.
+	#[must_use]
+	pub fn effect(&self, outcome: RollOutcome) -> &[String] {
+		let text = self.get(outcome);
+
+		if text.is_empty() {
+			match outcome {
+				RollOutcome::ExceptionalSuccess => &self.success,
+				RollOutcome::DramaticFailure => &self.failure,
+				_ => text,
+			}
+		} else {
+			text
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum RollOutcome {
+	#[strum(to_string = "Exceptional Success")]
+	ExceptionalSuccess,
+	Success,
+	Failure,
+	#[strum(to_string = "Dramatic Failure")]
+	DramaticFailure,
+}
+
+impl RollOutcome {
+	#[must_use]
+	pub fn is_success(&self) -> bool {
+		matches!(self, Self::ExceptionalSuccess | Self::Success)
+	}
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -54,6 +100,13 @@ pub struct ActionFields {
 	pub suggested_modifiers: SuggestedModifiers,
 }
 
+impl ActionFields {
+	#[must_use]
+	pub fn effect(&self, outcome: RollOutcome) -> &[String] {
+		self.roll_results.effect(outcome)
+	}
+}
+
 pub enum ItemKind {
 	Merit(Item<Merit>),
 	MoonGift(MoonGift),
@@ -64,12 +117,16 @@ pub enum ItemKind {
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Item<T> {
 	pub name: String,
+	#[serde(default)]
 	pub reference: BookReference,
 
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub description: Vec<String>,
 	#[serde(default, skip_serializing_if = "Vec::is_empty")]
 	pub effects: Vec<String>,
+	/// Mechanical effects applied to characters with the item.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub modifiers: Vec<ItemModifier>,
 
 	#[serde(flatten)]
 	pub inner: T,
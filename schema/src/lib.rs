@@ -6,6 +6,7 @@ pub mod dice_pool;
 pub mod dot_range;
 pub mod error;
 pub mod item;
+pub mod library;
 pub mod modifiers;
 pub mod prerequisites;
 pub mod template;
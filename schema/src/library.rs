@@ -0,0 +1,544 @@
+use std::collections::HashMap;
+#[cfg(feature = "fs")]
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use derive_more::Display;
+#[cfg(feature = "fs")]
+use thiserror::Error;
+
+use crate::{
+	book::{
+		Book, BookId, BookInfo, BookReference, MeritItem, MoonGift, OtherGift, SpellItem,
+		UnknownPrerequisite,
+	},
+	item::{
+		Item,
+		gift::{Facet, Gift, Moon, Other},
+	},
+	template::werewolf::Renown,
+};
+
+#[cfg(feature = "fs")]
+#[derive(Error, Debug)]
+pub enum LibraryError {
+	#[error("Couldn't read {path}: {source}")]
+	Io {
+		path: PathBuf,
+		source: std::io::Error,
+	},
+	#[error("Couldn't parse {path}: {source}")]
+	Ron {
+		path: PathBuf,
+		source: Box<ron::error::SpannedError>,
+	},
+	#[error("Couldn't parse {path}: {source}")]
+	Json {
+		path: PathBuf,
+		source: serde_json::Error,
+	},
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum ItemType {
+	Merit,
+	Spell,
+	#[display("Moon Gift")]
+	MoonGift,
+	Gift,
+}
+
+/// Two books defining the same item differently. The first definition is the one kept.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display("{kind} {name} is defined differently in {first} and {second}")]
+pub struct Conflict {
+	pub kind: ItemType,
+	pub name: String,
+	pub first: BookId,
+	pub second: BookId,
+}
+
+/// An item in a [`Library`], along with the gift a facet belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemRef<'a> {
+	Merit(&'a MeritItem),
+	Spell(&'a SpellItem),
+	MoonGift(&'a MoonGift, &'a Item<Facet<Moon>>),
+	Gift(&'a OtherGift, &'a Item<Facet<Other>>),
+}
+
+impl ItemRef<'_> {
+	#[must_use]
+	pub fn name(&self) -> &str {
+		match self {
+			ItemRef::Merit(item) => &item.name,
+			ItemRef::Spell(item) => &item.name,
+			ItemRef::MoonGift(_, facet) => &facet.name,
+			ItemRef::Gift(_, facet) => &facet.name,
+		}
+	}
+
+	#[must_use]
+	pub fn reference(&self) -> &BookReference {
+		match self {
+			ItemRef::Merit(item) => &item.reference,
+			ItemRef::Spell(item) => &item.reference,
+			ItemRef::MoonGift(_, facet) => &facet.reference,
+			ItemRef::Gift(_, facet) => &facet.reference,
+		}
+	}
+}
+
+/// Where an item is, by index into the library's books.
+#[derive(Debug, Clone, Copy)]
+enum Location {
+	Merit(usize, usize),
+	Spell(usize, usize),
+	MoonGift(usize, usize, usize),
+	Gift(usize, usize, usize),
+}
+
+/// A name without case, spaces or punctuation, so that `Fast Reflexes` matches the
+/// `fast-reflexes` merit.
+#[must_use]
+pub fn key(name: &str) -> String {
+	name.chars()
+		.filter(char::is_ascii_alphanumeric)
+		.map(|c| c.to_ascii_lowercase())
+		.collect()
+}
+
+/// Whether two items are the same apart from where they're printed.
+fn same_item<T: Clone + PartialEq>(a: &Item<T>, b: &Item<T>) -> bool {
+	let mut b = b.clone();
+	b.reference = a.reference.clone();
+	a == &b
+}
+
+/// The locations of deduplicated items, in the order they were loaded.
+fn load_order(items: &HashMap<String, (usize, usize)>) -> Vec<(usize, usize)> {
+	let mut locations: Vec<_> = items.values().copied().collect();
+	locations.sort_unstable();
+	locations
+}
+
+fn same_gift<T: Clone + PartialEq>(a: &Gift<T>, b: &Gift<T>) -> bool {
+	a.kind == b.kind
+		&& a.facets.len() == b.facets.len()
+		&& a.facets.iter().zip(&b.facets).all(|(a, b)| same_item(a, b))
+}
+
+/// Books indexed together, with items deduplicated across books by name.
+#[derive(Debug, Default, Clone)]
+pub struct Library {
+	books: Vec<Book>,
+
+	merits: HashMap<String, (usize, usize)>,
+	spells: HashMap<String, (usize, usize)>,
+	moon_gifts: HashMap<String, (usize, usize)>,
+	gifts: HashMap<String, (usize, usize)>,
+
+	pages: HashMap<BookReference, Vec<Location>>,
+	conflicts: Vec<Conflict>,
+}
+
+impl Library {
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Loads every `.ron` and `.json` book in a directory, in file name order.
+	///
+	/// # Errors
+	/// If the directory or one of the books can't be read or parsed.
+	#[cfg(feature = "fs")]
+	pub fn load_dir(path: impl AsRef<Path>) -> Result<Self, LibraryError> {
+		let path = path.as_ref();
+		let io = |source| LibraryError::Io {
+			path: path.to_owned(),
+			source,
+		};
+
+		let mut paths = Vec::new();
+		for entry in fs::read_dir(path).map_err(io)? {
+			let path = entry.map_err(io)?.path();
+			if matches!(
+				path.extension().and_then(|ext| ext.to_str()),
+				Some("ron" | "json")
+			) {
+				paths.push(path);
+			}
+		}
+		paths.sort();
+
+		let mut library = Self::new();
+		for path in paths {
+			library.add_book(Self::load_book(&path)?);
+		}
+		Ok(library)
+	}
+
+	/// Loads a single RON or JSON book, going by its extension, and parses prerequisites
+	/// stored as text.
+	///
+	/// # Errors
+	/// If the file can't be read or parsed.
+	#[cfg(feature = "fs")]
+	pub fn load_book(path: &Path) -> Result<Book, LibraryError> {
+		let str = fs::read_to_string(path).map_err(|source| LibraryError::Io {
+			path: path.to_owned(),
+			source,
+		})?;
+
+		let mut book: Book = if path.extension().is_some_and(|ext| ext == "json") {
+			serde_json::from_str(&str).map_err(|source| LibraryError::Json {
+				path: path.to_owned(),
+				source,
+			})?
+		} else {
+			ron::from_str(&str).map_err(|source| LibraryError::Ron {
+				path: path.to_owned(),
+				source: Box::new(source),
+			})?
+		};
+		book.reparse_prerequisites();
+
+		Ok(book)
+	}
+
+	/// Adds a book, keeping items already in the library and recording a [`Conflict`]
+	/// for any the book defines differently.
+	pub fn add_book(&mut self, book: Book) {
+		let b = self.books.len();
+		let id = book.info.id;
+
+		for (i, merit) in book.merits.iter().enumerate() {
+			self.index_page(&merit.reference, Location::Merit(b, i));
+
+			match self.merits.get(&key(&merit.name)) {
+				Some(&(first, j)) => {
+					if !same_item(&self.books[first].merits[j], merit) {
+						self.conflict(ItemType::Merit, &merit.name, first, id);
+					}
+				}
+				None => {
+					self.merits.insert(key(&merit.name), (b, i));
+				}
+			}
+		}
+
+		for (i, spell) in book.mage_spells.iter().enumerate() {
+			self.index_page(&spell.reference, Location::Spell(b, i));
+
+			match self.spells.get(&key(&spell.name)) {
+				Some(&(first, j)) => {
+					if !same_item(&self.books[first].mage_spells[j], spell) {
+						self.conflict(ItemType::Spell, &spell.name, first, id);
+					}
+				}
+				None => {
+					self.spells.insert(key(&spell.name), (b, i));
+				}
+			}
+		}
+
+		for (i, gift) in book.moon_gifts.iter().enumerate() {
+			for (f, facet) in gift.facets.iter().enumerate() {
+				self.index_page(&facet.reference, Location::MoonGift(b, i, f));
+			}
+
+			match self.moon_gifts.get(&key(&gift.name)) {
+				Some(&(first, j)) => {
+					if !same_gift(&self.books[first].moon_gifts[j], gift) {
+						self.conflict(ItemType::MoonGift, &gift.name, first, id);
+					}
+				}
+				None => {
+					self.moon_gifts.insert(key(&gift.name), (b, i));
+				}
+			}
+		}
+
+		for (i, gift) in book.gifts.iter().enumerate() {
+			for (f, facet) in gift.facets.iter().enumerate() {
+				self.index_page(&facet.reference, Location::Gift(b, i, f));
+			}
+
+			match self.gifts.get(&key(&gift.name)) {
+				Some(&(first, j)) => {
+					if !same_gift(&self.books[first].gifts[j], gift) {
+						self.conflict(ItemType::Gift, &gift.name, first, id);
+					}
+				}
+				None => {
+					self.gifts.insert(key(&gift.name), (b, i));
+				}
+			}
+		}
+
+		self.books.push(book);
+	}
+
+	/// Records an item as printed on the referenced page, unless its page isn't known.
+	fn index_page(&mut self, reference: &BookReference, location: Location) {
+		if reference.1 != 0 {
+			self.pages
+				.entry(reference.clone())
+				.or_default()
+				.push(location);
+		}
+	}
+
+	fn conflict(&mut self, kind: ItemType, name: &str, first: usize, second: BookId) {
+		self.conflicts.push(Conflict {
+			kind,
+			name: name.to_owned(),
+			first: self.books[first].info.id,
+			second,
+		});
+	}
+
+	pub fn books(&self) -> impl Iterator<Item = &Book> {
+		self.books.iter()
+	}
+
+	#[must_use]
+	pub fn book(&self, id: BookId) -> Option<&BookInfo> {
+		self.books
+			.iter()
+			.map(|book| &book.info)
+			.find(|info| info.id == id)
+	}
+
+	/// Items defined differently by two books.
+	#[must_use]
+	pub fn conflicts(&self) -> &[Conflict] {
+		&self.conflicts
+	}
+
+	/// Finds a merit by name, ignoring case and punctuation.
+	#[must_use]
+	pub fn merit(&self, name: &str) -> Option<&MeritItem> {
+		self.merits
+			.get(&key(name))
+			.map(|&(b, i)| &self.books[b].merits[i])
+	}
+
+	/// Every merit prerequisite that refers to a merit no loaded book defines, e.g.
+	/// because of a typo or a book that isn't loaded, since those can never be met.
+	#[must_use]
+	pub fn undefined_merits(&self) -> Vec<UnknownPrerequisite<'_>> {
+		let mut report = Vec::new();
+		for book in &self.books {
+			for (item, reference, prereqs) in book.item_prerequisites() {
+				for name in prereqs.merits() {
+					if self.merit(name).is_none() {
+						report.push(UnknownPrerequisite {
+							item: item.clone(),
+							reference,
+							prerequisite: name.to_owned(),
+						});
+					}
+				}
+			}
+		}
+		report
+	}
+
+	/// Every merit, in the order they were loaded.
+	pub fn merits(&self) -> impl Iterator<Item = &MeritItem> {
+		load_order(&self.merits)
+			.into_iter()
+			.map(|(b, i)| &self.books[b].merits[i])
+	}
+
+	/// Every Moon Gift, in the order they were loaded.
+	pub fn moon_gifts(&self) -> impl Iterator<Item = &MoonGift> {
+		load_order(&self.moon_gifts)
+			.into_iter()
+			.map(|(b, i)| &self.books[b].moon_gifts[i])
+	}
+
+	/// Finds a spell by name, ignoring case and punctuation.
+	#[must_use]
+	pub fn spell(&self, name: &str) -> Option<&SpellItem> {
+		self.spells
+			.get(&key(name))
+			.map(|&(b, i)| &self.books[b].mage_spells[i])
+	}
+
+	/// Finds a Moon Gift by name, ignoring case and punctuation.
+	#[must_use]
+	pub fn moon_gift(&self, name: &str) -> Option<&MoonGift> {
+		self.moon_gifts
+			.get(&key(name))
+			.map(|&(b, i)| &self.books[b].moon_gifts[i])
+	}
+
+	/// Finds a Shadow or Wolf Gift by name, ignoring case and punctuation.
+	#[must_use]
+	pub fn gift(&self, name: &str) -> Option<&OtherGift> {
+		self.gifts
+			.get(&key(name))
+			.map(|&(b, i)| &self.books[b].gifts[i])
+	}
+
+	/// Every Gift facet learned with the given Renown, along with its Gift, in the order
+	/// they were loaded.
+	pub fn gifts_for_renown(
+		&self,
+		renown: Renown,
+	) -> impl Iterator<Item = (&OtherGift, &Item<Facet<Other>>)> {
+		load_order(&self.gifts)
+			.into_iter()
+			.map(|(b, i)| &self.books[b].gifts[i])
+			.flat_map(move |gift| {
+				gift.facets
+					.iter()
+					.filter(move |facet| facet.inner.inner.renown == renown)
+					.map(move |facet| (gift, facet))
+			})
+	}
+
+	/// Every item printed on the referenced page, in the order they were loaded.
+	#[must_use]
+	pub fn page(&self, reference: &BookReference) -> Vec<ItemRef<'_>> {
+		self.pages
+			.get(reference)
+			.into_iter()
+			.flatten()
+			.map(|location| self.resolve(*location))
+			.collect()
+	}
+
+	fn resolve(&self, location: Location) -> ItemRef<'_> {
+		match location {
+			Location::Merit(b, i) => ItemRef::Merit(&self.books[b].merits[i]),
+			Location::Spell(b, i) => ItemRef::Spell(&self.books[b].mage_spells[i]),
+			Location::MoonGift(b, i, f) => {
+				let gift = &self.books[b].moon_gifts[i];
+				ItemRef::MoonGift(gift, &gift.facets[f])
+			}
+			Location::Gift(b, i, f) => {
+				let gift = &self.books[b].gifts[i];
+				ItemRef::Gift(gift, &gift.facets[f])
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+	use crate::{book::BookInfo, item::merit::Merit, prerequisites::Prerequisites};
+
+	fn merit(name: &str, reference: BookReference, notes: &[&str]) -> MeritItem {
+		Item {
+			name: name.to_owned(),
+			reference,
+			description: Vec::new(),
+			effects: Vec::new(),
+			modifiers: Vec::new(),
+			inner: Merit {
+				notes: notes.iter().map(ToString::to_string).collect(),
+				..Default::default()
+			},
+		}
+	}
+
+	#[test]
+	fn library() {
+		let mut core = Book::from(BookInfo::new(
+			"Chronicles of Darkness".to_owned(),
+			BookId::CofD,
+		));
+		core.merits = vec![
+			merit("Giant", BookReference(BookId::CofD, 59), &[]),
+			merit("Fast Reflexes", BookReference(BookId::CofD, 59), &[]),
+		];
+
+		let mut vampire = Book::from(BookInfo::new(
+			"Vampire: The Requiem".to_owned(),
+			BookId::VtR2e,
+		));
+		vampire.merits = vec![
+			merit("Giant", BookReference(BookId::VtR2e, 112), &[]),
+			merit(
+				"Fast Reflexes",
+				BookReference(BookId::VtR2e, 112),
+				&["Reprinted"],
+			),
+			merit("Feeding Grounds", BookReference(BookId::VtR2e, 112), &[]),
+		];
+
+		let mut library = Library::new();
+		library.add_book(core);
+		library.add_book(vampire);
+
+		assert_eq!(
+			library.merit("giant").unwrap().reference,
+			BookReference(BookId::CofD, 59)
+		);
+		let merits: Vec<_> = library.merits().map(|merit| merit.name.as_str()).collect();
+		assert_eq!(merits, ["Giant", "Fast Reflexes", "Feeding Grounds"]);
+		assert_eq!(
+			library.conflicts(),
+			[Conflict {
+				kind: ItemType::Merit,
+				name: "Fast Reflexes".to_owned(),
+				first: BookId::CofD,
+				second: BookId::VtR2e,
+			}]
+		);
+
+		let page: Vec<_> = library
+			.page(&BookReference(BookId::VtR2e, 112))
+			.iter()
+			.map(ItemRef::name)
+			.map(ToOwned::to_owned)
+			.collect();
+		assert_eq!(page, ["Giant", "Fast Reflexes", "Feeding Grounds"]);
+
+		let mut gifts: Book = ron::from_str(include_str!("../../lib/data/Gifts.ron")).unwrap();
+		gifts.reparse_prerequisites();
+		library.add_book(gifts);
+		assert!(
+			library
+				.gifts_for_renown(Renown::Purity)
+				.all(|(_, facet)| facet.inner.inner.renown == Renown::Purity)
+		);
+		assert!(library.gifts_for_renown(Renown::Purity).count() > 0);
+	}
+
+	#[test]
+	fn undefined_merits() {
+		let mut book = Book::from(BookInfo::new(
+			"Chronicles of Darkness".to_owned(),
+			BookId::CofD,
+		));
+		let mut fixer = merit("Fixer", BookReference(BookId::CofD, 45), &[]);
+		fixer.inner.prerequisites = Prerequisites::from_str("Contacts ••, Wits •••").unwrap();
+		let mut giant = merit("Giant", BookReference(BookId::CofD, 59), &[]);
+		giant.inner.prerequisites = Prerequisites::from_str("Strenght Performance •").unwrap();
+		book.merits = vec![
+			merit("Contacts", BookReference(BookId::CofD, 44), &[]),
+			fixer,
+			giant,
+		];
+
+		let mut library = Library::new();
+		library.add_book(book);
+
+		let undefined: Vec<_> = library
+			.undefined_merits()
+			.iter()
+			.map(ToString::to_string)
+			.collect();
+		assert_eq!(undefined, ["CofD pg.59: Giant: Strenght Performance"]);
+	}
+}
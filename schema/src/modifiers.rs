@@ -2,6 +2,11 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{
+	dice_pool::DicePool,
+	traits::{Trait, attribute::Attribute, skill::Skill},
+};
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
@@ -28,4 +33,181 @@ impl SuggestedModifiers {
 	pub fn is_empty(&self) -> bool {
 		self.0.is_empty()
 	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&str, i8)> {
+		self.0.iter().map(|(name, value)| (name.as_str(), *value))
+	}
+
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<i8> {
+		self.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value)
+	}
+}
+
+/// A mechanical effect of an item, e.g. a merit's bonus to Defense.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ItemModifier {
+	pub target: ModifierTarget,
+	pub value: ModifierValue,
+	#[serde(default, skip_serializing_if = "ModifierOp::is_add")]
+	pub op: ModifierOp,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub condition: Option<ModifierCondition>,
+	/// Lowest rating of the item the modifier applies at.
+	#[serde(default, skip_serializing_if = "is_zero")]
+	pub min_rating: u8,
+}
+
+impl ItemModifier {
+	#[must_use]
+	pub fn new(target: ModifierTarget, value: ModifierValue, op: ModifierOp) -> Self {
+		Self {
+			target,
+			value,
+			op,
+			condition: None,
+			min_rating: 0,
+		}
+	}
+
+	#[must_use]
+	pub fn with_condition(mut self, condition: ModifierCondition) -> Self {
+		self.condition = Some(condition);
+		self
+	}
+
+	#[must_use]
+	pub fn with_min_rating(mut self, min_rating: u8) -> Self {
+		self.min_rating = min_rating;
+		self
+	}
+
+	/// Whether the modifier applies when the item has the given rating.
+	#[must_use]
+	pub fn applies_at(&self, rating: u8) -> bool {
+		rating > 0 && rating >= self.min_rating
+	}
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(n: &u8) -> bool {
+	*n == 0
+}
+
+/// What an [`ItemModifier`] changes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum ModifierTarget {
+	/// Dots of the Attribute itself, which can't raise it above 5.
+	BaseAttribute(Attribute),
+	/// Dots of the Skill itself, which can't raise it above 5.
+	BaseSkill(Skill),
+	Trait(Trait),
+	/// The dice pool of every roll, e.g. a Tilt's penalty.
+	Roll,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum ModifierValue {
+	Num(i16),
+	/// The value for each dot of the item.
+	PerDot(i16),
+	/// The value at each rating of the item, starting at 1. Ratings past the end use
+	/// the last value.
+	ByRating(Vec<i16>),
+	/// Replaces the Skill used, e.g. Brawl for Defense.
+	Skill(Skill),
+	/// Replaces the dice pool used, e.g. the Attributes for Defense.
+	DicePool(DicePool),
+}
+
+impl ModifierValue {
+	/// The value at the item's rating, `None` if it isn't a number.
+	#[must_use]
+	pub fn at_rating(&self, rating: u8) -> Option<i16> {
+		match self {
+			ModifierValue::Num(value) => Some(*value),
+			ModifierValue::PerDot(value) => Some(value.saturating_mul(i16::from(rating))),
+			ModifierValue::ByRating(values) => Some(
+				values
+					.get(usize::from(rating).saturating_sub(1))
+					.or_else(|| values.last())
+					.copied()
+					.unwrap_or(0),
+			),
+			ModifierValue::Skill(_) | ModifierValue::DicePool(_) => None,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum ModifierOp {
+	#[default]
+	Add,
+	/// Replaces the trait's value.
+	Set,
+	/// The trait can't go below the value.
+	Min,
+	/// The trait can't go above the value.
+	Max,
+	/// Divides the trait by the value, rounding down.
+	Div,
+}
+
+impl ModifierOp {
+	#[allow(clippy::trivially_copy_pass_by_ref)]
+	fn is_add(&self) -> bool {
+		*self == ModifierOp::Add
+	}
+}
+
+/// When an [`ItemModifier`] applies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum ModifierCondition {
+	/// A Werewolf form, by name.
+	Form(String),
+	/// The roll uses this Skill.
+	Skill(Skill),
+	/// The character has the Specialty claimed for the roll.
+	Specialty,
+	/// A circumstance of the roll, e.g. `firearm` or `darkness`.
+	Tag(String),
+	All(Vec<ModifierCondition>),
+	Any(Vec<ModifierCondition>),
+	Not(Box<ModifierCondition>),
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::traits::DerivedTrait;
+
+	#[test]
+	fn item_modifier() {
+		let modifier = ItemModifier::new(
+			ModifierTarget::Trait(Trait::DerivedTrait(DerivedTrait::Defense)),
+			ModifierValue::ByRating(vec![1, 1, 2]),
+			ModifierOp::Add,
+		)
+		.with_condition(ModifierCondition::Not(Box::new(ModifierCondition::Tag(
+			"firearm".to_owned(),
+		))))
+		.with_min_rating(2);
+
+		let ron = ron::to_string(&modifier).unwrap();
+		assert_eq!(ron::from_str::<ItemModifier>(&ron).unwrap(), modifier);
+
+		assert!(!modifier.applies_at(1));
+		assert!(modifier.applies_at(2));
+		assert_eq!(modifier.value.at_rating(2), Some(1));
+		assert_eq!(modifier.value.at_rating(5), Some(2));
+		assert_eq!(ModifierValue::PerDot(2).at_rating(3), Some(6));
+	}
 }
@@ -1,4 +1,4 @@
-use std::{ops::Deref, str::FromStr};
+use std::{fmt, ops::Deref, str::FromStr};
 
 use derive_more::Display;
 use itertools::Itertools;
@@ -7,47 +7,273 @@ use serde::{Deserialize, Serialize};
 use crate::{
 	dot_range::{dots_to_num, num_to_dots},
 	error,
-	template::Template,
-	traits::Trait,
+	template::{self, Anchor, Template, mage::Arcanum, werewolf::Renown},
+	traits::{Trait, skill::Skill},
 };
 
+/// Splits `s` at each `separator` outside of parentheses.
+fn split_top_level<'a>(s: &'a str, separator: &str) -> Vec<&'a str> {
+	let mut parts = Vec::new();
+	let mut depth = 0usize;
+	let mut start = 0;
+
+	for (i, c) in s.char_indices() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			_ if depth == 0 && i >= start && s[i..].starts_with(separator) => {
+				parts.push(&s[start..i]);
+				start = i + separator.len();
+			}
+			_ => {}
+		}
+	}
+	parts.push(&s[start..]);
+
+	parts
+}
+
+/// The inside of `s` if all of it is one parenthesized group.
+fn strip_group(s: &str) -> Option<&str> {
+	let inner = s.strip_prefix('(')?.strip_suffix(')')?;
+	let mut depth = 0usize;
+	for c in inner.chars() {
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.checked_sub(1)?,
+			_ => {}
+		}
+	}
+	Some(inner)
+}
+
+/// Supernatural Tolerance is often rated with a number, e.g. "Blood Potency 2+".
+fn split_tolerance(s: &str) -> Option<(Trait, u8)> {
+	let (trait_, rating) = s.rsplit_once(' ')?;
+
+	match Trait::from_str(trait_) {
+		Ok(trait_ @ Trait::SupernaturalTolerance(_)) => {
+			Some((trait_, rating.trim_end_matches('+').parse().ok()?))
+		}
+		_ => None,
+	}
+}
+
+/// Whether any of several prerequisites is met, `None` if none is and some can't be told.
+fn any(results: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+	let mut result = Some(false);
+	for met in results {
+		match met {
+			Some(true) => return Some(true),
+			Some(false) => {}
+			None => result = None,
+		}
+	}
+	result
+}
+
+/// Whether all of several prerequisites are met, `None` if none is unmet and some can't be
+/// told.
+fn all(results: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+	let mut result = Some(true);
+	for met in results {
+		match met {
+			Some(false) => return Some(false),
+			Some(true) => {}
+			None => result = None,
+		}
+	}
+	result
+}
+
+/**
+ * Something prerequisites can be checked against, usually a character
+ */
+pub trait PrerequisiteSubject {
+	fn template(&self) -> Template;
+	/// The subject's rating in the trait, 0 if it doesn't have it.
+	fn trait_rating(&self, trait_: &Trait) -> u16;
+	/// The subject's rating in the merit, 0 if it doesn't have it.
+	fn merit_rating(&self, name: &str) -> u16;
+	/// The subject's rating in a supernatural ability, like a Discipline or an Arcanum.
+	fn ability_rating(&self, name: &str) -> u16;
+	fn has_specialty(&self, skill: Skill, specialty: &str) -> bool;
+	/// Whether the subject is still being created, rather than advancing with Experience.
+	fn is_in_creation(&self) -> bool;
+	/// The subject's Integrity, or its template's equivalent, like Humanity.
+	fn integrity(&self) -> u16;
+	/// Whether the subject belongs to the splat, e.g. the Mekhet clan or the Invictus.
+	fn has_splat(&self, name: &str) -> bool;
+	/// The Skill an "Any Skill" prerequisite is checked against, e.g. the one a merit was
+	/// taken for. `None` lets any Skill meet it.
+	fn chosen_skill(&self) -> Option<Skill> {
+		None
+	}
+}
+
+/// A subject that has chosen the Skill "Any Skill" prerequisites are checked against.
+pub struct ChosenSkill<'a, S>(pub &'a S, pub Skill);
+
+impl<S: PrerequisiteSubject> PrerequisiteSubject for ChosenSkill<'_, S> {
+	fn template(&self) -> Template {
+		self.0.template()
+	}
+
+	fn trait_rating(&self, trait_: &Trait) -> u16 {
+		self.0.trait_rating(trait_)
+	}
+
+	fn merit_rating(&self, name: &str) -> u16 {
+		self.0.merit_rating(name)
+	}
+
+	fn ability_rating(&self, name: &str) -> u16 {
+		self.0.ability_rating(name)
+	}
+
+	fn has_specialty(&self, skill: Skill, specialty: &str) -> bool {
+		self.0.has_specialty(skill, specialty)
+	}
+
+	fn is_in_creation(&self) -> bool {
+		self.0.is_in_creation()
+	}
+
+	fn integrity(&self) -> u16 {
+		self.0.integrity()
+	}
+
+	fn has_splat(&self, name: &str) -> bool {
+		self.0.has_splat(name)
+	}
+
+	fn chosen_skill(&self) -> Option<Skill> {
+		Some(self.1)
+	}
+}
+
 /**
  * Level-rated prerequisite types
  */
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
-#[serde(untagged)]
 pub enum RatedPrerequisiteKey {
+	/// Another merit by name.
+	Merit(String),
+	/// A Skill with a specialty in it, e.g. "Athletics (Running)".
+	#[display("{_0} ({_1})")]
+	Specialty(Skill, String),
+	/// A supernatural ability, e.g. "Forces", or a Discipline or Contract written as
+	/// `Ability("Protean")`, since the parser can't tell those from merit names.
+	Ability(String),
+	/// A Skill of the subject's choice, e.g. the one a merit is taken for.
+	#[display("Any Skill")]
+	AnySkill,
+	#[serde(untagged)]
 	Trait(Trait),
+	#[serde(untagged)]
 	Unknown(String),
 }
 
+impl From<Trait> for RatedPrerequisiteKey {
+	fn from(trait_: Trait) -> Self {
+		Self::Trait(trait_)
+	}
+}
+
 impl FromStr for RatedPrerequisiteKey {
 	type Err = error::ParseError;
 
 	fn from_str(prereq: &str) -> Result<Self, Self::Err> {
-		Trait::from_str(prereq)
-			.map(Self::Trait)
-			.or_else(|_| Ok(Self::Unknown(prereq.to_owned())))
+		Trait::from_str(prereq).map(Self::Trait).or_else(|_| {
+			Ok(prereq
+				.strip_suffix(')')
+				.and_then(|prereq| prereq.split_once(" ("))
+				.and_then(|(skill, specialty)| {
+					Skill::from_str(skill)
+						.ok()
+						.map(|skill| Self::Specialty(skill, specialty.to_owned()))
+				})
+				.unwrap_or_else(|| {
+					if Arcanum::from_str(prereq).is_ok() || Renown::from_str(prereq).is_ok() {
+						Self::Ability(prereq.to_owned())
+					} else if prereq.eq_ignore_ascii_case("Any Skill") {
+						Self::AnySkill
+					} else if prereq.starts_with("Any ") {
+						// A choice, like "Any Skill", rather than a name.
+						Self::Unknown(prereq.to_owned())
+					} else {
+						Self::Merit(prereq.to_owned())
+					}
+				}))
+		})
 	}
 }
 
 /**
  * Prerequisites with level ratings
  */
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
-#[display("{_0} {}", num_to_dots(*_1))]
 pub struct RatedPrerequisite(RatedPrerequisiteKey, u8);
 
+impl fmt::Display for RatedPrerequisite {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self(key @ RatedPrerequisiteKey::Specialty(..), 0) => write!(f, "{key} specialty"),
+			Self(key, rating) => write!(f, "{key} {}", num_to_dots(*rating)),
+		}
+	}
+}
+
+impl RatedPrerequisite {
+	#[must_use]
+	pub fn new(key: RatedPrerequisiteKey, rating: u8) -> Self {
+		Self(key, rating)
+	}
+
+	/// Whether the subject has the rating, `None` if the key isn't understood.
+	pub fn is_met(&self, subject: &impl PrerequisiteSubject) -> Option<bool> {
+		let rating = u16::from(self.1);
+		match &self.0 {
+			RatedPrerequisiteKey::Trait(trait_) => Some(subject.trait_rating(trait_) >= rating),
+			RatedPrerequisiteKey::Merit(name) => Some(subject.merit_rating(name) >= rating),
+			RatedPrerequisiteKey::Ability(name) => Some(subject.ability_rating(name) >= rating),
+			RatedPrerequisiteKey::AnySkill => Some(match subject.chosen_skill() {
+				Some(skill) => subject.trait_rating(&Trait::Skill(skill)) >= rating,
+				None => Skill::all()
+					.iter()
+					.any(|skill| subject.trait_rating(&Trait::Skill(*skill)) >= rating),
+			}),
+			RatedPrerequisiteKey::Specialty(skill, specialty) => Some(
+				subject.trait_rating(&Trait::Skill(*skill)) >= rating
+					&& subject.has_specialty(*skill, specialty),
+			),
+			RatedPrerequisiteKey::Unknown(_) => None,
+		}
+	}
+}
+
 impl FromStr for RatedPrerequisite {
 	type Err = error::ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		if let Some((prereq, dots)) = s.find(" •").map(|p| s.split_at(p)) {
 			dots_to_num(dots).and_then(|dots| {
-				RatedPrerequisiteKey::from_str(prereq).map(|prereq| RatedPrerequisite(prereq, dots))
+				RatedPrerequisiteKey::from_str(prereq.trim())
+					.map(|prereq| RatedPrerequisite(prereq, dots))
 			})
+		} else if let Some((trait_, rating)) = split_tolerance(s) {
+			Ok(RatedPrerequisite(
+				RatedPrerequisiteKey::Trait(trait_),
+				rating,
+			))
+		} else if let Some(Ok(key @ RatedPrerequisiteKey::Specialty(..))) = s
+			.strip_suffix(" specialty")
+			.map(RatedPrerequisiteKey::from_str)
+		{
+			// Any rating in the Skill will do
+			Ok(RatedPrerequisite(key, 0))
 		} else {
 			Err(error::ParseError::BadFormat(
 				"String is not in the format: {key} {dots}".to_owned(),
@@ -59,20 +285,166 @@ impl FromStr for RatedPrerequisite {
 /**
  * All prerequisite types
  */
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
-#[serde(untagged)]
 pub enum PrerequisiteKey {
+	/// Only met while the subject is being created.
+	#[display("Character creation only")]
+	CharacterCreation,
+	/// Met when the inner prerequisite isn't, e.g. "No Fame".
+	#[display("No {}", negated_name(_0))]
+	Not(Box<PrerequisiteKey>),
+	/// A group of prerequisites that must all be met, e.g. "(Dexterity •••, Brawl ••)".
+	#[display("({})", _0.iter().join(", "))]
+	All(Vec<Prerequisite>),
+	/// A bound on the subject's Integrity, e.g. "Integrity ≤ 5".
+	#[display("Integrity {_0}")]
+	Integrity(IntegrityRange),
+	/// The subject's template uses the Anchor, e.g. "Vice".
+	Anchor(Anchor),
+	/// A clan, auspice, order or other splat by name, e.g. "Mekhet".
+	Splat(String),
+	#[serde(untagged)]
 	Template(Template),
+	#[serde(untagged)]
 	Rated(RatedPrerequisite),
 }
 
+/// Integrity values a prerequisite allows.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum IntegrityRange {
+	#[display("≤ {_0}")]
+	AtMost(u8),
+	#[display("≥ {_0}")]
+	AtLeast(u8),
+}
+
+impl IntegrityRange {
+	#[must_use]
+	pub fn contains(self, integrity: u16) -> bool {
+		match self {
+			Self::AtMost(max) => integrity <= u16::from(max),
+			Self::AtLeast(min) => integrity >= u16::from(min),
+		}
+	}
+}
+
+impl FromStr for IntegrityRange {
+	type Err = error::ParseError;
+
+	/// Parses a bound like "≤ 5" or "< 5".
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let bad_format =
+			|| error::ParseError::BadFormat("String is not in the format: {op} {value}".to_owned());
+
+		let (op, value) = ["≤", "<=", "≥", ">=", "<", ">"]
+			.into_iter()
+			.find_map(|op| s.strip_prefix(op).map(|value| (op, value)))
+			.ok_or_else(bad_format)?;
+		let value: u8 = value.trim().parse().map_err(|_| bad_format())?;
+
+		match op {
+			"≤" | "<=" => Ok(Self::AtMost(value)),
+			"≥" | ">=" => Ok(Self::AtLeast(value)),
+			"<" => value
+				.checked_sub(1)
+				.map(Self::AtMost)
+				.ok_or_else(bad_format),
+			_ => value
+				.checked_add(1)
+				.map(Self::AtLeast)
+				.ok_or_else(bad_format),
+		}
+	}
+}
+
+/// An Integrity bound, e.g. "Integrity ≤ 5" or "Humanity < 5".
+fn parse_integrity(s: &str) -> Option<IntegrityRange> {
+	let split = s.find(['≤', '≥', '<', '>'])?;
+	let (name, range) = s.split_at(split);
+
+	template::Integrity::from_str(name.trim())
+		.ok()
+		.and_then(|_| IntegrityRange::from_str(range).ok())
+}
+
+/// The negated key as it's written after "No", leaving out the dot of "No Fame".
+fn negated_name(key: &PrerequisiteKey) -> String {
+	match key {
+		PrerequisiteKey::Rated(RatedPrerequisite(RatedPrerequisiteKey::Merit(name), 1)) => {
+			name.clone()
+		}
+		key => key.to_string(),
+	}
+}
+
+impl PrerequisiteKey {
+	pub fn is_met(&self, subject: &impl PrerequisiteSubject) -> Option<bool> {
+		match self {
+			Self::CharacterCreation => Some(subject.is_in_creation()),
+			Self::Not(key) => key.is_met(subject).map(|met| !met),
+			Self::All(prereqs) => all(prereqs.iter().map(|prereq| prereq.is_met(subject))),
+			Self::Integrity(range) => Some(range.contains(subject.integrity())),
+			Self::Anchor(anchor) => {
+				let template = subject.template();
+				Some(template.virtue_anchor() == *anchor || template.vice_anchor() == *anchor)
+			}
+			Self::Splat(name) => Some(subject.has_splat(name)),
+			Self::Template(template) => Some(subject.template() == *template),
+			Self::Rated(prereq) => prereq.is_met(subject),
+		}
+	}
+
+	fn unknowns(&self, unknowns: &mut Vec<String>) {
+		match self {
+			Self::Not(key) => key.unknowns(unknowns),
+			Self::All(prereqs) => prereqs.iter().for_each(|prereq| prereq.unknowns(unknowns)),
+			Self::Rated(prereq @ RatedPrerequisite(RatedPrerequisiteKey::Unknown(_), _)) => {
+				unknowns.push(prereq.to_string());
+			}
+			_ => {}
+		}
+	}
+
+	fn merits<'a>(&'a self, merits: &mut Vec<&'a str>) {
+		match self {
+			Self::Not(key) => key.merits(merits),
+			Self::All(prereqs) => prereqs.iter().for_each(|prereq| prereq.merits(merits)),
+			Self::Rated(RatedPrerequisite(RatedPrerequisiteKey::Merit(name), _)) => {
+				merits.push(name);
+			}
+			_ => {}
+		}
+	}
+}
+
 impl FromStr for PrerequisiteKey {
 	type Err = error::ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.eq_ignore_ascii_case("character creation only") {
+			return Ok(Self::CharacterCreation);
+		}
+		if let Some(range) = parse_integrity(s) {
+			return Ok(Self::Integrity(range));
+		}
+		// Merits are named without dots, so "No Fame" means not having any Fame.
+		if let Some(s) = s.strip_prefix("No ").or_else(|| s.strip_prefix("Not ")) {
+			return Ok(Self::Not(Box::new(Self::from_str(s).unwrap_or_else(
+				|_| {
+					Self::Rated(RatedPrerequisite(
+						RatedPrerequisiteKey::Merit(s.to_owned()),
+						1,
+					))
+				},
+			))));
+		}
+
 		Template::from_str(s)
 			.map(Self::Template)
+			.or_else(|_| Anchor::from_str(s).map(Self::Anchor))
 			.or_else(|_| RatedPrerequisite::from_str(s).map(Self::Rated))
 		// .or_else(|_| Ok(Self::Unknown(s.to_owned())))
 	}
@@ -81,7 +453,7 @@ impl FromStr for PrerequisiteKey {
 /**
  * A single prerequisite, or a set of OR prerequisites
  */
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Prerequisite {
@@ -91,30 +463,105 @@ pub enum Prerequisite {
 	Unknown(String),
 }
 
+impl From<PrerequisiteKey> for Prerequisite {
+	fn from(key: PrerequisiteKey) -> Self {
+		Self::Key(key)
+	}
+}
+
+impl Prerequisite {
+	/// A single rated prerequisite, e.g. "Wits •••".
+	#[must_use]
+	pub fn rated(key: impl Into<RatedPrerequisiteKey>, rating: u8) -> Self {
+		Self::Key(PrerequisiteKey::Rated(RatedPrerequisite(
+			key.into(),
+			rating,
+		)))
+	}
+
+	/// Whether the subject meets the prerequisite, `None` if that can't be told.
+	///
+	/// An OR prerequisite is met by any known alternative, and unmet only if every
+	/// alternative is known to be unmet.
+	pub fn is_met(&self, subject: &impl PrerequisiteSubject) -> Option<bool> {
+		match self {
+			Self::Key(key) => key.is_met(subject),
+			Self::Or(keys) => any(keys.iter().map(|key| key.is_met(subject))),
+			Self::Unknown(_) => None,
+		}
+	}
+
+	fn unknowns(&self, unknowns: &mut Vec<String>) {
+		match self {
+			Self::Key(key) => key.unknowns(unknowns),
+			Self::Or(keys) => keys.iter().for_each(|key| key.unknowns(unknowns)),
+			Self::Unknown(prereq) => unknowns.push(prereq.clone()),
+		}
+	}
+
+	fn merits<'a>(&'a self, merits: &mut Vec<&'a str>) {
+		match self {
+			Self::Key(key) => key.merits(merits),
+			Self::Or(keys) => keys.iter().for_each(|key| key.merits(merits)),
+			Self::Unknown(_) => {}
+		}
+	}
+}
+
 impl FromStr for Prerequisite {
 	type Err = error::ParseError;
 
 	fn from_str(prereq: &str) -> Result<Self, Self::Err> {
 		let prereq = prereq.trim();
-		PrerequisiteKey::from_str(prereq)
-			.map(Self::Key)
-			.or_else(|_| {
-				if let Some((l, r)) = prereq.split_once(" or ") {
-					let l = PrerequisiteKey::from_str(l.trim())?;
-					let r = PrerequisiteKey::from_str(r.trim())?;
-
-					Ok(Self::Or(vec![l, r]))
+		let unknown = || Self::Unknown(prereq.to_owned());
+
+		if let Some(group) = strip_group(prereq) {
+			let parts = split_top_level(group, ",");
+			return Ok(if parts.len() == 1 {
+				Self::from_str(group)?
+			} else {
+				let prereqs: Vec<_> = parts.into_iter().map(Self::from_str).try_collect()?;
+				if prereqs
+					.iter()
+					.any(|prereq| matches!(prereq, Self::Unknown(_)))
+				{
+					unknown()
 				} else {
-					Ok(Self::Unknown(prereq.to_owned()))
+					Self::Key(PrerequisiteKey::All(prereqs))
 				}
-			})
+			});
+		}
+
+		let alternatives = split_top_level(prereq, " or ");
+		if alternatives.len() == 1 {
+			return Ok(PrerequisiteKey::from_str(prereq).map_or_else(|_| unknown(), Self::Key));
+		}
+
+		// "Wits or Dexterity •••" rates every alternative with the last one's dots
+		let last = alternatives[alternatives.len() - 1];
+		let dots = last.find(" •").map(|p| &last[p..]);
+
+		let mut keys = Vec::new();
+		for alternative in alternatives {
+			let mut prereq = Self::from_str(alternative)?;
+			if let (Self::Unknown(_), Some(dots)) = (&prereq, dots) {
+				prereq = Self::from_str(&format!("{}{dots}", alternative.trim()))?;
+			}
+
+			match prereq {
+				Self::Key(key) => keys.push(key),
+				Self::Or(alternatives) => keys.extend(alternatives),
+				Self::Unknown(_) => return Ok(unknown()),
+			}
+		}
+		Ok(Self::Or(keys))
 	}
 }
 
 /**
  * A set of prerequisites (AND)
  */
-#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Display)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Display)]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[serde(transparent)]
 #[display("{}", _0.iter().join(", "))]
@@ -126,11 +573,85 @@ impl From<Vec<Prerequisite>> for Prerequisites {
 	}
 }
 
+impl FromIterator<Prerequisite> for Prerequisites {
+	fn from_iter<T: IntoIterator<Item = Prerequisite>>(iter: T) -> Self {
+		Prerequisites(iter.into_iter().collect())
+	}
+}
+
 impl Prerequisites {
 	#[must_use]
 	pub fn unwrap(self) -> Vec<Prerequisite> {
 		self.0
 	}
+
+	/// Sorts the prerequisites by whether the subject meets them.
+	pub fn check(&self, subject: &impl PrerequisiteSubject) -> PrerequisiteCheck<'_> {
+		let mut check = PrerequisiteCheck::default();
+		for prereq in &self.0 {
+			match prereq.is_met(subject) {
+				Some(true) => check.met.push(prereq),
+				Some(false) => check.unmet.push(prereq),
+				None => check.unknown.push(prereq),
+			}
+		}
+		check
+	}
+
+	/// Every part of the prerequisites that couldn't be parsed.
+	#[must_use]
+	pub fn unknowns(&self) -> Vec<String> {
+		let mut unknowns = Vec::new();
+		for prereq in &self.0 {
+			prereq.unknowns(&mut unknowns);
+		}
+		unknowns
+	}
+
+	/// The names of every merit the prerequisites refer to.
+	#[must_use]
+	pub fn merits(&self) -> Vec<&str> {
+		let mut merits = Vec::new();
+		for prereq in &self.0 {
+			prereq.merits(&mut merits);
+		}
+		merits
+	}
+
+	/// Parses prerequisites stored as text, e.g. by data written before the parser
+	/// understood them.
+	pub fn reparse(&mut self) {
+		self.0 = std::mem::take(&mut self.0)
+			.into_iter()
+			.flat_map(|prereq| match prereq {
+				Prerequisite::Unknown(s) => {
+					Self::from_str(&s).map_or_else(|_| vec![Prerequisite::Unknown(s)], Self::unwrap)
+				}
+				prereq => vec![prereq],
+			})
+			.collect();
+	}
+}
+
+/**
+ * The result of checking [`Prerequisites`] against a subject
+ */
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PrerequisiteCheck<'a> {
+	pub met: Vec<&'a Prerequisite>,
+	pub unmet: Vec<&'a Prerequisite>,
+	/// Prerequisites that couldn't be checked, e.g. ones that failed to parse.
+	pub unknown: Vec<&'a Prerequisite>,
+}
+
+impl PrerequisiteCheck<'_> {
+	/// Whether no prerequisite is known to be unmet.
+	///
+	/// Unknown prerequisites don't count against the subject.
+	#[must_use]
+	pub fn is_met(&self) -> bool {
+		self.unmet.is_empty()
+	}
 }
 
 impl Deref for Prerequisites {
@@ -145,12 +666,211 @@ impl FromStr for Prerequisites {
 	type Err = error::ParseError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let ok: Result<Vec<_>, _> = s
-			.split(", ")
-			.filter(|f| !f.is_empty())
+		let s = s.replace("<i>", "").replace("</i>", "");
+		let ok: Result<Vec<_>, _> = split_top_level(&s, ",")
+			.into_iter()
+			.filter(|f| !f.trim().is_empty())
 			.map(FromStr::from_str)
 			.collect();
 
 		Ok(Prerequisites(ok?))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{template::SupernaturalTolerance, traits::attribute::Attribute};
+
+	struct Subject;
+
+	impl PrerequisiteSubject for Subject {
+		fn template(&self) -> Template {
+			Template::Vampire
+		}
+
+		fn trait_rating(&self, trait_: &Trait) -> u16 {
+			match trait_ {
+				Trait::Attribute(Attribute::Resolve) => 3,
+				Trait::Skill(Skill::Occult) => 1,
+				_ => 0,
+			}
+		}
+
+		fn merit_rating(&self, name: &str) -> u16 {
+			u16::from(name == "Contacts") * 2
+		}
+
+		fn ability_rating(&self, name: &str) -> u16 {
+			u16::from(name == "Celerity") * 2
+		}
+
+		fn has_specialty(&self, skill: Skill, specialty: &str) -> bool {
+			skill == Skill::Occult && specialty == "Ghosts"
+		}
+
+		fn is_in_creation(&self) -> bool {
+			true
+		}
+
+		fn integrity(&self) -> u16 {
+			6
+		}
+
+		fn has_splat(&self, name: &str) -> bool {
+			name == "Mekhet"
+		}
+	}
+
+	#[test]
+	fn check() {
+		let prereqs = Prerequisites::from_str(
+			"Resolve •••, Occult •• or Vampire, Wits ••• or Mage, Contacts ••, Occult •• or Any Mental Skill •••",
+		)
+		.unwrap();
+		let check = prereqs.check(&Subject);
+
+		assert_eq!(check.met, vec![&prereqs[0], &prereqs[1], &prereqs[3]]);
+		assert_eq!(check.unmet, vec![&prereqs[2]]);
+		assert_eq!(check.unknown, vec![&prereqs[4]]);
+		assert!(!check.is_met());
+		assert_eq!(
+			prereqs[3],
+			Prerequisite::Key(PrerequisiteKey::Rated(RatedPrerequisite::new(
+				RatedPrerequisiteKey::Merit("Contacts".to_owned()),
+				2
+			)))
+		);
+	}
+
+	#[test]
+	fn keys() {
+		let prereqs = Prerequisites::from_str(
+			"Occult (Ghosts) •, Occult (Vampires) •, No Fame, Not Vampire, Character creation only",
+		)
+		.unwrap();
+		assert_eq!(
+			prereqs[0],
+			Prerequisite::Key(PrerequisiteKey::Rated(RatedPrerequisite::new(
+				RatedPrerequisiteKey::Specialty(Skill::Occult, "Ghosts".to_owned()),
+				1
+			)))
+		);
+		assert_eq!(prereqs[2].to_string(), "No Fame");
+		assert_eq!(
+			Prerequisites::from_str(&prereqs.to_string()).unwrap(),
+			prereqs
+		);
+
+		let check = prereqs.check(&Subject);
+		assert_eq!(check.met, vec![&prereqs[0], &prereqs[2], &prereqs[4]]);
+		assert_eq!(check.unmet, vec![&prereqs[1], &prereqs[3]]);
+
+		let fixer: Prerequisites = ron::from_str(r#"[(Merit("Contacts"), 2), (Wits, 3)]"#).unwrap();
+		assert_eq!(fixer.check(&Subject).unmet, vec![&fixer[1]]);
+		assert_eq!(
+			ron::from_str::<Prerequisites>(&ron::to_string(&prereqs).unwrap()).unwrap(),
+			prereqs
+		);
+	}
+
+	#[test]
+	fn parse() {
+		let rated = |key, rating| PrerequisiteKey::Rated(RatedPrerequisite::new(key, rating));
+		let trait_ = |trait_: Trait, rating| rated(RatedPrerequisiteKey::Trait(trait_), rating);
+
+		let prereqs = Prerequisites::from_str(
+			"Wits <i>or</i> Dexterity or Strength •••, (Brawl •• or Weaponry ••), \
+			 Strength ••• or (Dexterity •••, Athletics (Running) ••), Celerity •••, \
+			 Blood Potency 2, Firearms (Revolvers) specialty",
+		)
+		.unwrap();
+
+		assert_eq!(
+			prereqs[0],
+			Prerequisite::Or(vec![
+				trait_(Attribute::Wits.into(), 3),
+				trait_(Attribute::Dexterity.into(), 3),
+				trait_(Attribute::Strength.into(), 3),
+			])
+		);
+		assert_eq!(
+			prereqs[1],
+			Prerequisite::Or(vec![
+				trait_(Skill::Brawl.into(), 2),
+				trait_(Skill::Weaponry.into(), 2)
+			])
+		);
+		assert_eq!(
+			prereqs[2],
+			Prerequisite::Or(vec![
+				trait_(Attribute::Strength.into(), 3),
+				PrerequisiteKey::All(vec![
+					Prerequisite::Key(trait_(Attribute::Dexterity.into(), 3)),
+					Prerequisite::Key(rated(
+						RatedPrerequisiteKey::Specialty(Skill::Athletics, "Running".to_owned()),
+						2
+					)),
+				]),
+			])
+		);
+		assert_eq!(
+			prereqs[3],
+			Prerequisite::Key(rated(RatedPrerequisiteKey::Merit("Celerity".to_owned()), 3))
+		);
+		assert_eq!(
+			prereqs[4],
+			Prerequisite::Key(trait_(SupernaturalTolerance::BloodPotency.into(), 2))
+		);
+		assert!(prereqs.unknowns().is_empty());
+		assert_eq!(
+			Prerequisites::from_str(&prereqs.to_string()).unwrap(),
+			prereqs
+		);
+
+		assert_eq!(
+			Prerequisites::from_str("Any Mental Skill •••, Contacts •• or Vampire")
+				.unwrap()
+				.unknowns(),
+			vec!["Any Mental Skill •••"]
+		);
+	}
+
+	#[test]
+	fn choices() {
+		let prereqs =
+			Prerequisites::from_str("Any Skill •, Humanity < 7, Integrity ≥ 7, Vice, Mask")
+				.unwrap();
+		assert_eq!(
+			prereqs[1],
+			Prerequisite::Key(PrerequisiteKey::Integrity(IntegrityRange::AtMost(6)))
+		);
+		assert_eq!(prereqs[1].to_string(), "Integrity ≤ 6");
+		assert!(prereqs.unknowns().is_empty());
+		assert_eq!(
+			Prerequisites::from_str(&prereqs.to_string()).unwrap(),
+			prereqs
+		);
+		assert_eq!(
+			ron::from_str::<Prerequisites>(&ron::to_string(&prereqs).unwrap()).unwrap(),
+			prereqs
+		);
+
+		let check = prereqs.check(&Subject);
+		assert_eq!(check.met, vec![&prereqs[0], &prereqs[1], &prereqs[4]]);
+		assert_eq!(check.unmet, vec![&prereqs[2], &prereqs[3]]);
+
+		let any_skill = Prerequisites::from_str("Any Skill •").unwrap();
+		assert!(
+			!any_skill
+				.check(&ChosenSkill(&Subject, Skill::Brawl))
+				.is_met()
+		);
+
+		let mekhet = Prerequisites::from(vec![
+			PrerequisiteKey::Splat("Mekhet".to_owned()).into(),
+			Prerequisite::rated(Trait::from(Attribute::Resolve), 3),
+		]);
+		assert!(mekhet.check(&Subject).is_met());
+	}
+}
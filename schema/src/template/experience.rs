@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+use super::Template;
+
+/// The kind of trait being bought with Experience.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PurchaseKind {
+	Attribute,
+	Skill,
+	Specialty,
+	Merit,
+	/// The template's ability, e.g. Disciplines or Arcana.
+	/// `affinity` is set for in-clan Disciplines and the like.
+	Ability {
+		affinity: bool,
+	},
+	SupernaturalTolerance,
+}
+
+/// Which Experience pool pays for a purchase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Payment {
+	Experience,
+	/// Arcane Experience, Blood Experience, etc.
+	AlternateExperience,
+	/// Alternate Experience if there is enough, Experience otherwise.
+	Either,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExperienceCost {
+	pub per_dot: u16,
+	pub payment: Payment,
+}
+
+impl ExperienceCost {
+	const fn new(per_dot: u16, payment: Payment) -> Self {
+		Self { per_dot, payment }
+	}
+
+	#[must_use]
+	pub fn cost(&self, from: u16, to: u16) -> u16 {
+		self.per_dot * to.saturating_sub(from)
+	}
+}
+
+impl Template {
+	/// Experience cost of a trait for this template, `None` if it can't be bought.
+	#[must_use]
+	pub fn experience_cost(&self, kind: PurchaseKind) -> Option<ExperienceCost> {
+		use Payment::{AlternateExperience, Either, Experience};
+
+		match (self, kind) {
+			(_, PurchaseKind::Attribute) => Some(ExperienceCost::new(4, Experience)),
+			(_, PurchaseKind::Skill) => Some(ExperienceCost::new(2, Experience)),
+			(_, PurchaseKind::Specialty | PurchaseKind::Merit) => {
+				Some(ExperienceCost::new(1, Experience))
+			}
+
+			(Template::Mage, PurchaseKind::Ability { .. }) => {
+				Some(ExperienceCost::new(4, AlternateExperience))
+			}
+			(Template::Mage, PurchaseKind::SupernaturalTolerance) => {
+				Some(ExperienceCost::new(5, AlternateExperience))
+			}
+
+			(Template::Vampire, PurchaseKind::Ability { affinity }) => {
+				Some(ExperienceCost::new(if affinity { 3 } else { 4 }, Either))
+			}
+			(Template::Vampire, PurchaseKind::SupernaturalTolerance) => {
+				Some(ExperienceCost::new(5, Either))
+			}
+
+			(Template::Werewolf | Template::Bound, PurchaseKind::Ability { .. }) => {
+				Some(ExperienceCost::new(3, Experience))
+			}
+			(Template::Werewolf | Template::Changeling, PurchaseKind::SupernaturalTolerance) => {
+				Some(ExperienceCost::new(5, Experience))
+			}
+
+			_ => None,
+		}
+	}
+}
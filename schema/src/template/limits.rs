@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use super::Template;
+
+/// Highest rating of Supernatural Tolerance.
+pub const MAX_TOLERANCE: u16 = 10;
+/// Highest rating of most traits for mortals and low-tolerance characters.
+pub const MORTAL_TRAIT_MAX: u16 = 5;
+
+/// Max fuel by Supernatural Tolerance, starting at 1.
+const MAX_FUEL: [u16; 10] = [10, 11, 12, 13, 15, 20, 25, 30, 50, 75];
+/// Fuel that can be spent per turn by Supernatural Tolerance, starting at 1.
+const FUEL_PER_TURN: [u16; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 10, 15];
+/// Attribute and Skill maximums of templates whose tolerance raises them past 5.
+const TRAIT_MAX: [u16; 10] = [5, 5, 5, 5, 5, 6, 7, 8, 9, 10];
+/// Trait maximums that stay at 5 whatever the tolerance.
+const FLAT_MAX: [u16; 10] = [MORTAL_TRAIT_MAX; 10];
+/// Highest Arcanum by Gnosis.
+const ARCANUM_MAX: [u16; 10] = [3, 3, 4, 4, 5, 5, 5, 5, 5, 5];
+
+/// A template's [`TraitLimits`] at each rating of its Supernatural Tolerance, starting at 1.
+struct LimitsTable {
+	trait_max: [u16; 10],
+	ability_max: [u16; 10],
+	max_fuel: [u16; 10],
+	fuel_per_turn: [u16; 10],
+}
+
+/// Blood Potency raises Disciplines along with Attributes and Skills.
+const VAMPIRE: LimitsTable = LimitsTable {
+	trait_max: TRAIT_MAX,
+	ability_max: TRAIT_MAX,
+	max_fuel: MAX_FUEL,
+	fuel_per_turn: FUEL_PER_TURN,
+};
+const MAGE: LimitsTable = LimitsTable {
+	trait_max: TRAIT_MAX,
+	ability_max: ARCANUM_MAX,
+	max_fuel: MAX_FUEL,
+	fuel_per_turn: FUEL_PER_TURN,
+};
+/// Werewolves and changelings, whose Renown and Contracts stay at 5.
+const STANDARD: LimitsTable = LimitsTable {
+	trait_max: TRAIT_MAX,
+	ability_max: FLAT_MAX,
+	max_fuel: MAX_FUEL,
+	fuel_per_turn: FUEL_PER_TURN,
+};
+/// Synergy doesn't raise any trait past 5.
+const BOUND: LimitsTable = LimitsTable {
+	trait_max: FLAT_MAX,
+	ability_max: FLAT_MAX,
+	max_fuel: MAX_FUEL,
+	fuel_per_turn: FUEL_PER_TURN,
+};
+
+/// Limits set on a character's traits by their template and Supernatural Tolerance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraitLimits {
+	/// Highest rating of Attributes and Skills.
+	pub trait_max: u16,
+	/// Highest rating of the template's abilities, e.g. Disciplines or Arcana.
+	pub ability_max: u16,
+	pub max_fuel: u16,
+	pub fuel_per_turn: u16,
+}
+
+impl TraitLimits {
+	pub const MORTAL: Self = Self {
+		trait_max: MORTAL_TRAIT_MAX,
+		ability_max: MORTAL_TRAIT_MAX,
+		max_fuel: 0,
+		fuel_per_turn: 0,
+	};
+}
+
+impl Template {
+	/// Trait limits at the given rating of Supernatural Tolerance.
+	///
+	/// Templates without Supernatural Tolerance always get [`TraitLimits::MORTAL`], as do
+	/// characters whose tolerance is 0.
+	#[must_use]
+	pub fn limits(&self, power: u16) -> TraitLimits {
+		let table = match self {
+			Template::Vampire => &VAMPIRE,
+			Template::Mage => &MAGE,
+			Template::Werewolf | Template::Changeling => &STANDARD,
+			Template::Bound => &BOUND,
+			_ => return TraitLimits::MORTAL,
+		};
+		if power == 0 {
+			return TraitLimits::MORTAL;
+		}
+
+		let i = usize::from(power.min(MAX_TOLERANCE) - 1);
+
+		TraitLimits {
+			trait_max: table.trait_max[i],
+			ability_max: table.ability_max[i],
+			max_fuel: table.max_fuel[i],
+			fuel_per_turn: table.fuel_per_turn[i],
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn limits() {
+		assert_eq!(Template::Mortal.limits(3), TraitLimits::MORTAL);
+		assert_eq!(Template::Vampire.limits(0), TraitLimits::MORTAL);
+
+		let vampire = Template::Vampire.limits(7);
+		assert_eq!((vampire.trait_max, vampire.ability_max), (7, 7));
+		assert_eq!((vampire.max_fuel, vampire.fuel_per_turn), (25, 7));
+
+		assert_eq!(Template::Mage.limits(1).ability_max, 3);
+		assert_eq!(Template::Mage.limits(7).ability_max, 5);
+		assert_eq!(Template::Werewolf.limits(7).ability_max, 5);
+		assert_eq!(Template::Bound.limits(10).trait_max, 5);
+		assert_eq!(Template::Changeling.limits(12).max_fuel, 75);
+	}
+}
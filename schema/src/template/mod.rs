@@ -2,10 +2,15 @@ use cofd_util::{SplatEnum, VariantName};
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, Display, EnumString};
 
+mod experience;
+mod limits;
 pub mod mage;
 mod vampire;
 pub mod werewolf;
 
+pub use experience::{ExperienceCost, Payment, PurchaseKind};
+pub use limits::{MAX_TOLERANCE, MORTAL_TRAIT_MAX, TraitLimits};
+
 #[derive(
 	SplatEnum,
 	Debug,
@@ -17,6 +22,7 @@ pub mod werewolf;
 	Display,
 	PartialEq,
 	Eq,
+	Hash,
 	VariantName,
 )]
 #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
@@ -153,9 +159,11 @@ pub enum SupernaturalTolerance {
 	AsRefStr,
 	PartialEq,
 	Eq,
+	Hash,
 	Display,
 	VariantName,
 )]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 #[strum(ascii_case_insensitive)]
 pub enum Anchor {
 	Virtue,